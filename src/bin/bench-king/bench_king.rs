@@ -0,0 +1,508 @@
+use bench_king_sleeper::calculation_helpers::calculate_bench_decisions::{
+    calculate_bench_decisions_for_week, BenchDecisionReport,
+};
+use bench_king_sleeper::calculation_helpers::calculate_bench_king_for_week::{
+    calculate_bench_king_for_owner, calculate_bench_king_for_week,
+};
+use bench_king_sleeper::calculation_helpers::calculate_best_ball::calculate_best_ball_reports;
+use bench_king_sleeper::calculation_helpers::calculate_efficiency::calculate_efficiency_leaderboard;
+use bench_king_sleeper::calculation_helpers::calculate_matchup_outcomes::{
+    calculate_alternate_standings, calculate_matchup_outcomes_for_week,
+};
+use bench_king_sleeper::calculation_helpers::report::{round_to_two_decimals, Report};
+use bench_king_sleeper::client::SleeperClient;
+use bench_king_sleeper::history::HistoryStore;
+use bench_king_sleeper::models::injury::InjurySnapshot;
+use bench_king_sleeper::models::schedule::Schedule;
+use bench_king_sleeper::models::{league::League, player::Players};
+use bench_king_sleeper::webhook::{post_webhook, webhook_payload, WebhookFormat};
+use clap::Args;
+use futures::future::join_all;
+use serde::Serialize;
+
+use crate::config::Settings;
+use crate::history::rosters_for_week;
+use crate::output::{emit, OutputFormat};
+
+#[derive(Args, Debug)]
+pub struct BenchKingArgs {
+    #[arg(
+        long,
+        env = "SLEEPER_USER_ID",
+        long_help = "Sleeper user ID or username; calculates that user's bench regret across all of their leagues for the season"
+    )]
+    user: Option<String>,
+    #[arg(
+        long,
+        long_help = "Flag to calculate the season through --week",
+        default_value = "false"
+    )]
+    season_to_date: bool,
+    #[arg(
+        long,
+        long_help = "Flag to show season to date standings as if every manager started their optimal lineup",
+        default_value = "false"
+    )]
+    alternate_standings: bool,
+    #[arg(
+        long,
+        env = "NFL_SCHEDULE_FILE",
+        long_help = "JSON file with the NFL schedule, used to calculate achievable optimal points given lineup locks"
+    )]
+    schedule_file: Option<String>,
+    #[arg(
+        long,
+        env = "BENCH_KING_INJURY_DIR",
+        long_help = "Directory of weekly injury snapshots, used to flag benched players who were out, doubtful or on bye"
+    )]
+    injury_dir: Option<String>,
+    #[arg(
+        long,
+        long_help = "Flag to save the current injury statuses as the snapshot for the week, run before lineups lock",
+        default_value = "false",
+        requires = "injury_dir"
+    )]
+    save_injury_snapshot: bool,
+    #[arg(
+        long,
+        env = "BENCH_KING_HISTORY_DB",
+        long_help = "SQLite database of saved weeks, used for who owned each roster in past weeks"
+    )]
+    history_db: Option<String>,
+    #[arg(
+        long,
+        env = "BENCH_KING_WEBHOOK_URL",
+        conflicts_with = "user",
+        long_help = "Discord or Slack incoming webhook URL to post the report to, not supported with --user"
+    )]
+    webhook_url: Option<String>,
+    #[arg(
+        long,
+        long_help = "Webhook payload format, discord or slack",
+        default_value = "discord"
+    )]
+    webhook_format: WebhookFormat,
+    #[arg(
+        long,
+        long_help = "Flag to print the webhook payload instead of posting it, not supported with --user",
+        default_value = "false",
+        conflicts_with = "user"
+    )]
+    dry_run: bool,
+}
+
+pub async fn run(
+    sleeper_client: &SleeperClient,
+    settings: &Settings,
+    args: BenchKingArgs,
+) -> Result<(), String> {
+    if let Some(user) = &args.user {
+        return run_for_user(sleeper_client, settings, &args, user).await;
+    }
+    let league_id = settings.league_id()?;
+    let week = settings.week;
+    let rosters = sleeper_client
+        .get_rosters_in_league(league_id.clone())
+        .await?;
+    let players = sleeper_client.fetch_all_players().await?;
+    let league = sleeper_client.get_league_details(league_id.clone()).await?;
+    let owners = sleeper_client
+        .get_users_in_league(league_id.clone())
+        .await?;
+    let labels = settings.labels(&owners);
+    let schedule = match &args.schedule_file {
+        Some(path) => Some(Schedule::from_file(path)?),
+        None => None,
+    };
+    let history = match &args.history_db {
+        Some(path) => Some(HistoryStore::open(path)?),
+        None => None,
+    };
+    if league.is_best_ball() {
+        // Sleeper starts the optimal lineup in best-ball leagues, so there is no bench king
+        let first_week = if args.season_to_date { 1 } else { week };
+        let mut weeks = vec![];
+        for week in first_week..=week {
+            weeks.push(
+                sleeper_client
+                    .get_league_matchups_for_week(league_id.clone(), week)
+                    .await?,
+            );
+        }
+        let mut reports = calculate_best_ball_reports(&weeks, &rosters, &players);
+        reports.retain(|r| !settings.is_excluded(r.roster_id));
+        return emit(settings.format, &reports, |reports| {
+            reports
+                .iter()
+                .map(|r| format!("Best Ball: {}", r.describe(&labels)))
+                .collect()
+        });
+    }
+    if args.alternate_standings {
+        let mut weeks = vec![];
+        for week in 1..=week {
+            let matchups = sleeper_client
+                .get_league_matchups_for_week(league_id.clone(), week)
+                .await?;
+            weeks.push(calculate_matchup_outcomes_for_week(
+                week,
+                matchups,
+                &rosters_for_week(history.as_ref(), &league_id, week, &rosters)?,
+                &players,
+                &league.roster_positions,
+            )?);
+        }
+        let mut standings = calculate_alternate_standings(&weeks);
+        standings.retain(|s| !settings.is_excluded(s.roster_id));
+        return emit(settings.format, &standings, |standings| {
+            standings
+                .iter()
+                .enumerate()
+                .map(|(idx, standing)| {
+                    let owner_name = rosters
+                        .iter()
+                        .find(|r| r.roster_id == standing.roster_id)
+                        .map(|r| labels.label(r.roster_id, &r.owner_id))
+                        .unwrap_or(standing.roster_id.to_string());
+                    format!(
+                        "Optimal Standings Rank: {}: {} ({})",
+                        idx + 1,
+                        standing,
+                        owner_name
+                    )
+                })
+                .collect()
+        });
+    }
+    let mut optimals;
+    let mut decisions = None;
+    let mut leaderboard = None;
+    if args.season_to_date {
+        let mut reports = vec![];
+        for week in 1..=week {
+            let matchups = sleeper_client
+                .get_league_matchups_for_week(league_id.clone(), week)
+                .await?;
+            reports.extend(calculate_bench_king_for_week(
+                week,
+                matchups,
+                &rosters_for_week(history.as_ref(), &league_id, week, &rosters)?,
+                &players,
+                &league,
+                schedule.as_ref(),
+            )?);
+        }
+        reports.retain(|r| !settings.is_excluded(r.roster_id));
+        let summaries = calculate_efficiency_leaderboard(reports);
+        optimals = summaries
+            .iter()
+            .map(|s| s.season_report())
+            .collect::<Vec<_>>();
+        leaderboard = Some(summaries);
+    } else {
+        let matchups = sleeper_client
+            .get_league_matchups_for_week(league_id.clone(), week)
+            .await?;
+        if let Some(injury_dir) = &args.injury_dir {
+            if args.save_injury_snapshot {
+                InjurySnapshot::from_players(settings.season.clone(), week, &players)
+                    .save(injury_dir)?;
+            }
+            match InjurySnapshot::load(injury_dir, &settings.season, week) {
+                Ok(injuries) => {
+                    let mut week_decisions = calculate_bench_decisions_for_week(
+                        week,
+                        &matchups,
+                        &players,
+                        &league.roster_positions,
+                        &injuries,
+                        schedule.as_ref(),
+                    );
+                    week_decisions.retain(|d| !settings.is_excluded(d.roster_id));
+                    decisions = Some(week_decisions);
+                }
+                Err(e) => eprintln!("No injury snapshot for week {}: {}", week, e),
+            }
+        }
+        optimals = calculate_bench_king_for_week(
+            week,
+            matchups,
+            &rosters,
+            &players,
+            &league,
+            schedule.as_ref(),
+        )?;
+    }
+    optimals.retain(|r| !settings.is_excluded(r.roster_id));
+    optimals.sort_by_key(|a| -(a.difference() as i32));
+    if args.webhook_url.is_some() || args.dry_run {
+        let title = if args.season_to_date {
+            format!("{} Bench King, season through week {}", league.name, week)
+        } else {
+            format!("{} Week {} Bench King", league.name, week)
+        };
+        let payload = webhook_payload(args.webhook_format, &title, &optimals, &labels);
+        if args.dry_run {
+            return emit(OutputFormat::Json, &payload, |_| vec![]);
+        }
+        if let Some(url) = &args.webhook_url {
+            post_webhook(url, &payload).await?;
+        }
+    }
+    if let Some(leaderboard) = &leaderboard {
+        if settings.format == OutputFormat::Json {
+            return emit(settings.format, leaderboard, |_| vec![]);
+        }
+        for (idx, summary) in leaderboard.iter().enumerate() {
+            println!(
+                "Efficiency Rank: {}: {}",
+                idx + 1,
+                summary.describe(&labels)
+            );
+        }
+    }
+    let ranks = |optimals: &[Report]| {
+        optimals
+            .iter()
+            .enumerate()
+            .map(|(idx, optimal)| {
+                format!(
+                    "Bench King Rank: {}: {}",
+                    idx + 1,
+                    optimal.describe(&labels)
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    let Some(bench_decisions) = decisions else {
+        return emit(settings.format, &optimals, |optimals| ranks(optimals));
+    };
+    let week = WeekWithDecisions {
+        reports: optimals,
+        bench_decisions,
+    };
+    emit(settings.format, &week, |week| {
+        let mut lines = week
+            .bench_decisions
+            .iter()
+            .map(|decision| {
+                let owner_id = rosters
+                    .iter()
+                    .find(|r| r.roster_id == decision.roster_id)
+                    .map(|r| r.owner_id.clone())
+                    .unwrap_or_default();
+                format!(
+                    "Bench Decisions: {}",
+                    decision.describe(&labels, &owner_id, &players)
+                )
+            })
+            .collect::<Vec<_>>();
+        lines.extend(ranks(&week.reports));
+        lines
+    })
+}
+
+/// A week's bench king reports alongside the decisions behind them, when an
+/// injury snapshot for the week was available.
+#[derive(Serialize)]
+struct WeekWithDecisions {
+    reports: Vec<Report>,
+    bench_decisions: Vec<BenchDecisionReport>,
+}
+
+#[derive(Serialize)]
+struct LeagueBenchReport {
+    league_id: String,
+    league_name: String,
+    report: Report,
+}
+
+async fn league_bench_report(
+    sleeper_client: &SleeperClient,
+    league: League,
+    user_id: &str,
+    week: i32,
+    players: &Players,
+    schedule: Option<&Schedule>,
+) -> Result<Option<LeagueBenchReport>, String> {
+    let rosters = sleeper_client
+        .get_rosters_in_league(league.league_id.clone())
+        .await?;
+    let matchups = sleeper_client
+        .get_league_matchups_for_week(league.league_id.clone(), week)
+        .await?;
+    let report = calculate_bench_king_for_owner(
+        week, user_id, matchups, &rosters, players, &league, schedule,
+    )?;
+    Ok(report.map(|report| LeagueBenchReport {
+        league_id: league.league_id,
+        league_name: league.name,
+        report,
+    }))
+}
+
+/// Bench regret for one user across every league they're in for the season.
+async fn run_for_user(
+    sleeper_client: &SleeperClient,
+    settings: &Settings,
+    args: &BenchKingArgs,
+    user: &str,
+) -> Result<(), String> {
+    let user = sleeper_client.get_user(user.to_string()).await?;
+    let leagues = sleeper_client
+        .get_all_leagues_for_user(
+            user.user_id.clone(),
+            settings.season.clone(),
+            settings.sport(),
+        )
+        .await?;
+    let players = sleeper_client.fetch_all_players().await?;
+    let schedule = match &args.schedule_file {
+        Some(path) => Some(Schedule::from_file(path)?),
+        None => None,
+    };
+    // Best-ball leagues start the optimal lineup, so there's no regret to report
+    let (best_ball, leagues): (Vec<League>, Vec<League>) =
+        leagues.into_iter().partition(|l| l.is_best_ball());
+    let league_names = leagues.iter().map(|l| l.name.clone()).collect::<Vec<_>>();
+    let results = join_all(leagues.into_iter().map(|league| {
+        league_bench_report(
+            sleeper_client,
+            league,
+            &user.user_id,
+            settings.week,
+            &players,
+            schedule.as_ref(),
+        )
+    }))
+    .await;
+    // One league failing shouldn't hide the others, so errors are reported and skipped
+    let mut reports = vec![];
+    let mut failed = 0;
+    for (league_name, result) in league_names.iter().zip(results) {
+        match result {
+            Ok(Some(report)) => reports.push(report),
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("Unable to calculate bench king for {}: {}", league_name, e);
+                failed += 1;
+            }
+        }
+    }
+    reports.sort_by(|a, b| {
+        b.report
+            .difference()
+            .partial_cmp(&a.report.difference())
+            .unwrap()
+    });
+    emit(settings.format, &reports, |reports| {
+        let mut lines = reports
+            .iter()
+            .map(|r| {
+                format!(
+                    "{}: scored {} points, optimal score is {}, left {} points on the bench",
+                    r.league_name,
+                    round_to_two_decimals(r.report.actual_points),
+                    round_to_two_decimals(r.report.optimal_points),
+                    round_to_two_decimals(r.report.difference())
+                )
+            })
+            .collect::<Vec<_>>();
+        for league in &best_ball {
+            lines.push(format!("{}: best ball, no bench decisions", league.name));
+        }
+        lines.push(format!(
+            "{} left {} points on benches across {} leagues in week {}",
+            user.display_name,
+            round_to_two_decimals(reports.iter().map(|r| r.report.difference()).sum()),
+            reports.len(),
+            settings.week
+        ));
+        if failed > 0 {
+            lines.push(format!(
+                "{} leagues failed and aren't included in the total",
+                failed
+            ));
+        }
+        lines
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use axum::extract::State;
+    use axum::routing::post;
+    use axum::Router;
+    use clap::Parser;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::serve::test::{serve, sleeper_stand_in, LEAGUE_ID};
+    use crate::Cli;
+
+    /// Stands in for the chat service, keeping the last payload posted to it.
+    async fn webhook_stand_in() -> (String, Arc<Mutex<Option<Value>>>) {
+        let posted = Arc::new(Mutex::new(None));
+        let app = Router::new()
+            .route(
+                "/webhook",
+                post(
+                    |State(posted): State<Arc<Mutex<Option<Value>>>>, body: String| async move {
+                        *posted.lock().unwrap() = Some(serde_json::from_str(&body).unwrap());
+                    },
+                ),
+            )
+            .with_state(posted.clone());
+        (format!("{}webhook", serve(app).await), posted)
+    }
+
+    #[test]
+    fn webhooks_are_rejected_for_users() {
+        let parse =
+            |args: &[&str]| Cli::try_parse_from([&["bench-king", "bench-king"], args].concat());
+        assert!(parse(&["--user", "bob", "--dry-run"]).is_err());
+        assert!(parse(&["--user", "bob", "--webhook-url", "http://localhost"]).is_err());
+        assert!(parse(&["--dry-run"]).is_ok());
+    }
+
+    #[tokio::test]
+    async fn season_to_date_json_posts_the_webhook() {
+        let (webhook_url, posted) = webhook_stand_in().await;
+        let client = SleeperClient::build_with_cache_dir(
+            std::env::temp_dir().join("bench-king-season-webhook-test"),
+        )
+        .with_base_url(sleeper_stand_in().await);
+        let settings = Settings {
+            league_id: Some(LEAGUE_ID.to_string()),
+            season: "2024".to_string(),
+            week: 2,
+            sport: "nfl".to_string(),
+            format: OutputFormat::Json,
+            cache_dir: None,
+            team_names: false,
+            excluded_rosters: vec![],
+        };
+        let args = BenchKingArgs {
+            user: None,
+            season_to_date: true,
+            alternate_standings: false,
+            schedule_file: None,
+            injury_dir: None,
+            save_injury_snapshot: false,
+            history_db: None,
+            webhook_url: Some(webhook_url),
+            webhook_format: WebhookFormat::Discord,
+            dry_run: false,
+        };
+        run(&client, &settings, args).await.unwrap();
+
+        let payload = posted.lock().unwrap().take().unwrap();
+        assert_eq!(
+            payload["embeds"][0]["title"],
+            "Fixture League Bench King, season through week 2"
+        );
+        assert_eq!(payload["embeds"][0]["fields"].as_array().unwrap().len(), 2);
+    }
+}
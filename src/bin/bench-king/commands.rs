@@ -0,0 +1,960 @@
+use std::collections::BTreeMap;
+
+use bench_king_sleeper::calculation_helpers::calculate_bench_king_for_week::calculate_bench_king_for_week;
+use bench_king_sleeper::calculation_helpers::calculate_decision_quality::calculate_decision_quality_for_week;
+use bench_king_sleeper::calculation_helpers::calculate_draft_grades::{
+    calculate_draft_grades, DraftPickValue,
+};
+use bench_king_sleeper::calculation_helpers::calculate_free_agents::{
+    calculate_free_agents, calculate_free_agents_at_week,
+};
+use bench_king_sleeper::calculation_helpers::calculate_player_usage::PlayerUsage;
+use bench_king_sleeper::calculation_helpers::calculate_rosters_at_week::calculate_rosters_at_week;
+use bench_king_sleeper::calculation_helpers::calculate_trades::{
+    calculate_trade_analyses, calculate_trade_ledgers, TradeAnalysis, TradeLedger,
+};
+use bench_king_sleeper::calculation_helpers::calculate_waiver_wire_king::calculate_waiver_wire_king;
+use bench_king_sleeper::calculation_helpers::calculate_waivers::calculate_waiver_reports;
+use bench_king_sleeper::calculation_helpers::calculate_weekly_recap::calculate_weekly_recap;
+use bench_king_sleeper::calculation_helpers::report::OwnerLabels;
+use bench_king_sleeper::client::{ActionType, SleeperClient, WinnerOrLoser, MAX_TRENDING_LIMIT};
+use bench_king_sleeper::dashboard::{render_dashboard, write_dashboard};
+use bench_king_sleeper::history::HistoryStore;
+use bench_king_sleeper::models::{matchup::Matchup, player::Players, roster::Roster};
+use bench_king_sleeper::recap::{render_recap, RecapFormat};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+use crate::config::Settings;
+use crate::history::rosters_for_week;
+use crate::output::emit;
+
+/// Last week of the NFL regular season. Fantasy playoffs finish within the
+/// regular season, so it's also the last week a league can score.
+pub const LAST_WEEK: i32 = 18;
+
+#[derive(Subcommand, Debug)]
+pub enum PlayersCommand {
+    /// Search players by name
+    Search { query: String },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LeagueCommand {
+    /// Show league details and settings
+    Info,
+}
+
+#[derive(Args, Debug)]
+pub struct TrendingArgs {
+    #[arg(
+        long,
+        long_help = "Flag to show the most dropped players instead of the most added",
+        default_value = "false"
+    )]
+    drop: bool,
+    #[arg(
+        long,
+        long_help = "Hours to look back, up to a week",
+        default_value = "24"
+    )]
+    lookback_hours: u32,
+    #[arg(
+        long,
+        long_help = "Number of players to return, up to 200",
+        default_value = "25",
+        value_parser = clap::value_parser!(u32).range(1..=MAX_TRENDING_LIMIT as i64)
+    )]
+    limit: u32,
+    #[arg(
+        long,
+        long_help = "Flag to only show players not on a roster in --league-id",
+        default_value = "false"
+    )]
+    available: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct FreeAgentsArgs {
+    #[arg(long, long_help = "Number of players to return", default_value = "25")]
+    limit: usize,
+}
+
+#[derive(Args, Debug)]
+pub struct RostersArgs {
+    #[arg(
+        long,
+        long_help = "Flag to show rosters as they were for --week, rebuilt from that week's matchups and the transactions log",
+        default_value = "false"
+    )]
+    historical: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct BracketArgs {
+    #[arg(
+        long,
+        long_help = "Flag to show the losers bracket",
+        default_value = "false"
+    )]
+    losers: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct RecapArgs {
+    #[arg(
+        long,
+        long_help = "Handlebars template file to render the recap with, defaults to the built-in template"
+    )]
+    template: Option<String>,
+    #[arg(
+        long,
+        long_help = "Flag to render HTML instead of Markdown",
+        default_value = "false"
+    )]
+    html: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct DashboardArgs {
+    #[arg(
+        long,
+        long_help = "Directory to write the dashboard to",
+        default_value = "dashboard"
+    )]
+    out_dir: String,
+    #[arg(
+        long,
+        env = "BENCH_KING_HISTORY_DB",
+        long_help = "SQLite database of saved weeks, used for who owned each roster in past weeks"
+    )]
+    history_db: Option<String>,
+}
+
+fn player_name(players: &Players, player_id: &str) -> String {
+    players
+        .get(player_id)
+        .map(|p| p.name())
+        .unwrap_or(player_id.to_string())
+}
+
+fn roster_label(labels: &OwnerLabels, rosters: &[Roster], roster_id: u32) -> String {
+    rosters
+        .iter()
+        .find(|r| r.roster_id == roster_id)
+        .map(|r| labels.label(r.roster_id, &r.owner_id))
+        .unwrap_or(format!("Roster {}", roster_id))
+}
+
+#[derive(Serialize)]
+pub struct StandingRow {
+    roster_id: u32,
+    owner: String,
+    wins: u16,
+    losses: u16,
+    ties: u16,
+    points_for: f32,
+    points_against: f32,
+}
+
+pub async fn standings(client: &SleeperClient, settings: &Settings) -> Result<(), String> {
+    let rows = standing_rows(client, settings, settings.league_id()?).await?;
+    emit(settings.format, &rows, |rows| {
+        rows.iter()
+            .enumerate()
+            .map(|(idx, r)| {
+                format!(
+                    "{}. {} {}-{}-{}, {:.2} points for, {:.2} points against",
+                    idx + 1,
+                    r.owner,
+                    r.wins,
+                    r.losses,
+                    r.ties,
+                    r.points_for,
+                    r.points_against
+                )
+            })
+            .collect()
+    })
+}
+
+/// Standings sorted by wins, then points for.
+pub async fn standing_rows(
+    client: &SleeperClient,
+    settings: &Settings,
+    league_id: String,
+) -> Result<Vec<StandingRow>, String> {
+    let rosters = client.get_rosters_in_league(league_id.clone()).await?;
+    let labels = settings.labels(&client.get_users_in_league(league_id).await?);
+    let mut rows = rosters
+        .iter()
+        .filter(|r| !settings.is_excluded(r.roster_id))
+        .map(|r| StandingRow {
+            roster_id: r.roster_id,
+            owner: labels.label(r.roster_id, &r.owner_id),
+            wins: r.settings.wins,
+            losses: r.settings.losses,
+            ties: r.settings.ties,
+            points_for: r.settings.points_for(),
+            points_against: r.settings.points_against(),
+        })
+        .collect::<Vec<_>>();
+    rows.sort_by(|a, b| {
+        b.wins
+            .cmp(&a.wins)
+            .then(b.points_for.partial_cmp(&a.points_for).unwrap())
+    });
+    Ok(rows)
+}
+
+pub async fn matchups(client: &SleeperClient, settings: &Settings) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let mut matchups = client
+        .get_league_matchups_for_week(league_id.clone(), settings.week)
+        .await?;
+    matchups.retain(|m| !settings.is_excluded(m.roster_id));
+    let rosters = client.get_rosters_in_league(league_id.clone()).await?;
+    let labels = settings.labels(&client.get_users_in_league(league_id).await?);
+    emit(settings.format, &matchups, |matchups| {
+        let mut by_matchup: BTreeMap<u16, Vec<String>> = BTreeMap::new();
+        for m in matchups {
+            by_matchup.entry(m.matchup_id).or_default().push(format!(
+                "{} {:.2}",
+                roster_label(&labels, &rosters, m.roster_id),
+                m.points
+            ));
+        }
+        by_matchup
+            .into_iter()
+            .map(|(matchup_id, sides)| format!("Matchup {}: {}", matchup_id, sides.join(" vs ")))
+            .collect()
+    })
+}
+
+pub async fn rosters(
+    client: &SleeperClient,
+    settings: &Settings,
+    args: RostersArgs,
+) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let mut rosters = client.get_rosters_in_league(league_id.clone()).await?;
+    rosters.retain(|r| !settings.is_excluded(r.roster_id));
+    let labels = settings.labels(&client.get_users_in_league(league_id.clone()).await?);
+    let players = client.fetch_all_players().await?;
+    if args.historical {
+        let matchups = client
+            .get_league_matchups_for_week(league_id.clone(), settings.week)
+            .await?;
+        let transactions = client
+            .get_transactions_through(league_id, LAST_WEEK)
+            .await?;
+        let rosters_at_week =
+            calculate_rosters_at_week(settings.week, &matchups, &rosters, &transactions);
+        return emit(settings.format, &rosters_at_week, |rosters| {
+            rosters
+                .iter()
+                .map(|r| {
+                    let names = r
+                        .players
+                        .iter()
+                        .map(|p| {
+                            let marker = if r.starters.contains(p) { "*" } else { "" };
+                            let acquired = r
+                                .acquisition(p)
+                                .map(|a| format!(" ({:?} week {})", a.transaction_type, a.week))
+                                .unwrap_or_default();
+                            format!("{}{}{}", player_name(&players, p), marker, acquired)
+                        })
+                        .collect::<Vec<_>>();
+                    format!(
+                        "Week {} {}: {}",
+                        r.week,
+                        labels.label(r.roster_id, &r.owner_id),
+                        names.join(", ")
+                    )
+                })
+                .collect()
+        });
+    }
+    emit(settings.format, &rosters, |rosters| {
+        rosters
+            .iter()
+            .map(|r| {
+                let names = r
+                    .players
+                    .iter()
+                    .flatten()
+                    .map(|p| {
+                        let marker = if r.starters.contains(p) { "*" } else { "" };
+                        format!("{}{}", player_name(&players, p), marker)
+                    })
+                    .collect::<Vec<_>>();
+                format!(
+                    "{}: {}",
+                    labels.label(r.roster_id, &r.owner_id),
+                    names.join(", ")
+                )
+            })
+            .collect()
+    })
+}
+
+pub async fn players(
+    client: &SleeperClient,
+    settings: &Settings,
+    command: PlayersCommand,
+) -> Result<(), String> {
+    match command {
+        PlayersCommand::Search { query } => {
+            let query = query.to_lowercase().replace(' ', "");
+            let players = client.fetch_all_players().await?;
+            let mut matches = players
+                .into_values()
+                .filter(|p| {
+                    p.search_full_name
+                        .as_ref()
+                        .is_some_and(|name| name.contains(&query))
+                })
+                .collect::<Vec<_>>();
+            matches.sort_by_key(|p| p.search_rank.unwrap_or(u32::MAX));
+            emit(settings.format, &matches, |matches| {
+                matches
+                    .iter()
+                    .map(|p| {
+                        format!(
+                            "{} ({}, {}) id {}{}",
+                            p.name(),
+                            p.position
+                                .map(|pos| format!("{:?}", pos))
+                                .unwrap_or_default(),
+                            p.team.clone().unwrap_or("FA".to_string()),
+                            p.player_id,
+                            p.injury_status
+                                .as_ref()
+                                .map(|s| format!(", {}", s))
+                                .unwrap_or_default()
+                        )
+                    })
+                    .collect()
+            })
+        }
+    }
+}
+
+pub async fn trending(
+    client: &SleeperClient,
+    settings: &Settings,
+    args: TrendingArgs,
+) -> Result<(), String> {
+    let action_type = if args.drop {
+        ActionType::Drop
+    } else {
+        ActionType::Add
+    };
+    // Sleeper applies the limit before the roster filter, so fetch as many as
+    // it allows and apply --limit afterwards
+    let request_limit = if args.available {
+        MAX_TRENDING_LIMIT
+    } else {
+        args.limit
+    };
+    let trending = client
+        .get_trending_players(
+            action_type,
+            settings.sport(),
+            Some(args.lookback_hours),
+            Some(request_limit),
+        )
+        .await?;
+    let rosters = if args.available {
+        client.get_rosters_in_league(settings.league_id()?).await?
+    } else {
+        vec![]
+    };
+    let players = client.fetch_all_players().await?;
+    let trending = trending
+        .iter()
+        .filter(|t| !rosters.iter().any(|r| r.has_player(&t.player_id)))
+        .take(args.limit as usize)
+        .map(|t| t.with_details(&players))
+        .collect::<Vec<_>>();
+    emit(settings.format, &trending, |trending| {
+        trending
+            .iter()
+            .map(|t| {
+                format!(
+                    "{} ({} {}){}: {}",
+                    t.name,
+                    t.position
+                        .map(|p| format!("{:?}", p))
+                        .unwrap_or("-".to_string()),
+                    t.team.as_deref().unwrap_or("FA"),
+                    t.injury_status
+                        .as_ref()
+                        .map(|s| format!(" [{}]", s))
+                        .unwrap_or_default(),
+                    t.count
+                )
+            })
+            .collect()
+    })
+}
+
+pub async fn free_agents(
+    client: &SleeperClient,
+    settings: &Settings,
+    args: FreeAgentsArgs,
+) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let league = client.get_league_details(league_id.clone()).await?;
+    let rosters = client.get_rosters_in_league(league_id).await?;
+    let players = client.fetch_all_players().await?;
+    let mut free_agents =
+        calculate_free_agents(&players, &rosters, &league.roster_positions, &league.sport);
+    free_agents.truncate(args.limit);
+    emit(settings.format, &free_agents, |free_agents| {
+        free_agents
+            .iter()
+            .map(|p| {
+                format!(
+                    "{} ({}, {}) id {}",
+                    p.name(),
+                    p.position
+                        .map(|pos| format!("{:?}", pos))
+                        .unwrap_or_default(),
+                    p.team.clone().unwrap_or("FA".to_string()),
+                    p.player_id
+                )
+            })
+            .collect()
+    })
+}
+
+pub async fn waiver_wire_king(client: &SleeperClient, settings: &Settings) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let week = settings.week;
+    let league = client.get_league_details(league_id.clone()).await?;
+    let rosters = client.get_rosters_in_league(league_id.clone()).await?;
+    let labels = settings.labels(&client.get_users_in_league(league_id.clone()).await?);
+    let mut matchups = client
+        .get_league_matchups_for_week(league_id.clone(), week)
+        .await?;
+    let transactions = client
+        .get_transactions_through(league_id, LAST_WEEK)
+        .await?;
+    let players = client.fetch_all_players().await?;
+    let weights = league.scoring_settings.weights();
+    let players_points = client
+        .get_player_stats_for_week(settings.sport(), league.season.clone(), week)
+        .await?
+        .iter()
+        .map(|s| (s.player_id.clone(), s.points(&weights)))
+        .collect();
+    let rosters_at_week = calculate_rosters_at_week(week, &matchups, &rosters, &transactions);
+    let pool = calculate_free_agents_at_week(
+        &players,
+        &rosters_at_week,
+        &league.roster_positions,
+        &league.sport,
+    );
+    // Excluded rosters' players still aren't free agents, so only filter now
+    matchups.retain(|m| !settings.is_excluded(m.roster_id));
+    let king = calculate_waiver_wire_king(
+        week,
+        &pool,
+        &players_points,
+        &league.roster_positions,
+        &matchups,
+    );
+    emit(settings.format, &king, |king| {
+        let mut lines = vec![format!(
+            "Waiver Wire King for week {}: {:.2} points from unrostered players",
+            king.week, king.points
+        )];
+        lines.extend(king.lineup.iter().map(|s| {
+            format!(
+                "{:?}: {} {:.2}",
+                s.slot,
+                player_name(&players, &s.player_id),
+                s.points
+            )
+        }));
+        let outscored = king
+            .teams_outscored
+            .iter()
+            .map(|r| roster_label(&labels, &rosters, *r))
+            .collect::<Vec<_>>();
+        if !outscored.is_empty() {
+            lines.push(format!("Outscored: {}", outscored.join(", ")));
+        }
+        lines
+    })
+}
+
+pub async fn decision_quality(client: &SleeperClient, settings: &Settings) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let week = settings.week;
+    let league = client.get_league_details(league_id.clone()).await?;
+    let rosters = client.get_rosters_in_league(league_id.clone()).await?;
+    let labels = settings.labels(&client.get_users_in_league(league_id.clone()).await?);
+    let matchups = client.get_league_matchups_for_week(league_id, week).await?;
+    let players = client.fetch_all_players().await?;
+    let weights = league.scoring_settings.weights();
+    let projected_points = client
+        .get_player_projections_for_week(settings.sport(), league.season.clone(), week)
+        .await?
+        .iter()
+        .map(|p| (p.player_id.clone(), p.points(&weights)))
+        .collect();
+    let mut decisions = calculate_decision_quality_for_week(
+        week,
+        &matchups,
+        &players,
+        &league.roster_positions,
+        &projected_points,
+    );
+    decisions.retain(|d| !settings.is_excluded(d.roster_id));
+    decisions.sort_by(|a, b| {
+        b.bad_decision_points()
+            .partial_cmp(&a.bad_decision_points())
+            .unwrap()
+    });
+    emit(settings.format, &decisions, |decisions| {
+        decisions
+            .iter()
+            .map(|d| {
+                let owner_id = rosters
+                    .iter()
+                    .find(|r| r.roster_id == d.roster_id)
+                    .map(|r| r.owner_id.as_str())
+                    .unwrap_or_default();
+                d.describe(&labels, owner_id)
+            })
+            .collect()
+    })
+}
+
+pub async fn transactions(client: &SleeperClient, settings: &Settings) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let transactions = client
+        .get_transactions(league_id.clone(), settings.week)
+        .await?;
+    let rosters = client.get_rosters_in_league(league_id.clone()).await?;
+    let labels = settings.labels(&client.get_users_in_league(league_id).await?);
+    let players = client.fetch_all_players().await?;
+    emit(settings.format, &transactions, |transactions| {
+        let names = |ids: Vec<String>| {
+            ids.iter()
+                .map(|p| player_name(&players, p))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        transactions
+            .iter()
+            .flat_map(|t| {
+                t.roster_ids.iter().map(|roster_id| {
+                    format!(
+                        "{:?} ({}): {} added [{}] dropped [{}]",
+                        t.transaction_type,
+                        t.status,
+                        roster_label(&labels, &rosters, *roster_id),
+                        names(t.added_by(*roster_id)),
+                        names(t.dropped_by(*roster_id))
+                    )
+                })
+            })
+            .collect()
+    })
+}
+
+/// Matchups for weeks 1 through --week, `weeks[0]` being week 1.
+async fn season_matchups(
+    client: &SleeperClient,
+    settings: &Settings,
+) -> Result<Vec<Vec<Matchup>>, String> {
+    let league_id = settings.league_id()?;
+    let mut weeks = vec![];
+    for week in 1..=settings.week {
+        weeks.push(
+            client
+                .get_league_matchups_for_week(league_id.clone(), week)
+                .await?,
+        );
+    }
+    Ok(weeks)
+}
+
+#[derive(Serialize)]
+struct TradeReport {
+    trades: Vec<TradeAnalysis>,
+    ledgers: Vec<TradeLedger>,
+}
+
+pub async fn trades(client: &SleeperClient, settings: &Settings) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let league = client.get_league_details(league_id.clone()).await?;
+    let rosters = client.get_rosters_in_league(league_id.clone()).await?;
+    let labels = settings.labels(&client.get_users_in_league(league_id.clone()).await?);
+    let players = client.fetch_all_players().await?;
+    let transactions = client
+        .get_transactions_through(league_id, settings.week)
+        .await?;
+    let weeks = season_matchups(client, settings).await?;
+    let usage = PlayerUsage::calculate(&weeks, &players, &league.roster_positions);
+    let trades = calculate_trade_analyses(&transactions, &usage);
+    let mut ledgers = calculate_trade_ledgers(&trades);
+    ledgers.retain(|l| !settings.is_excluded(l.roster_id));
+    let report = TradeReport { trades, ledgers };
+    emit(settings.format, &report, |report| {
+        let label = |roster_id| roster_label(&labels, &rosters, roster_id);
+        let names = |ids: &[String]| {
+            ids.iter()
+                .map(|p| player_name(&players, p))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut lines = report
+            .trades
+            .iter()
+            .map(|trade| {
+                let sides = trade
+                    .sides
+                    .iter()
+                    .map(|s| {
+                        format!(
+                            "{} got [{}] for {:.2} started ({:.2} optimal)",
+                            label(s.roster_id),
+                            names(&s.received),
+                            s.started_points,
+                            s.optimal_points
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                format!(
+                    "Week {} trade: {}; winner: {}",
+                    trade.week,
+                    sides.join("; "),
+                    trade.winner().map(label).unwrap_or("even".to_string())
+                )
+            })
+            .collect::<Vec<_>>();
+        lines.extend(report.ledgers.iter().map(|l| {
+            format!(
+                "Trade Ledger: {} made {} trades, won {}, gained {:.2}, gave up {:.2}, net {:.2}",
+                label(l.roster_id),
+                l.trades,
+                l.wins,
+                l.points_gained,
+                l.points_given_up,
+                l.net_points()
+            )
+        }));
+        lines
+    })
+}
+
+pub async fn waivers(client: &SleeperClient, settings: &Settings) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let league = client.get_league_details(league_id.clone()).await?;
+    let rosters = client.get_rosters_in_league(league_id.clone()).await?;
+    let labels = settings.labels(&client.get_users_in_league(league_id.clone()).await?);
+    let players = client.fetch_all_players().await?;
+    let transactions = client
+        .get_transactions_through(league_id, settings.week)
+        .await?;
+    let weeks = season_matchups(client, settings).await?;
+    let usage = PlayerUsage::calculate(&weeks, &players, &league.roster_positions);
+    let mut reports = calculate_waiver_reports(
+        &transactions,
+        &usage,
+        &rosters,
+        league.settings.waiver_budget,
+    );
+    reports.retain(|r| !settings.is_excluded(r.roster_id));
+    reports.sort_by(|a, b| b.started_points().partial_cmp(&a.started_points()).unwrap());
+    emit(settings.format, &reports, |reports| {
+        let per_dollar = |points: Option<f32>| {
+            points
+                .map(|p| format!("{:.2}", p))
+                .unwrap_or("n/a".to_string())
+        };
+        reports
+            .iter()
+            .map(|r| {
+                let mut line = format!(
+                    "{}: spent ${} of ${}, {} pickups started {:.2} ({:.2} optimal), {} started and {} optimal points per dollar",
+                    roster_label(&labels, &rosters, r.roster_id),
+                    r.waiver_budget_used,
+                    r.waiver_budget,
+                    r.pickups.len(),
+                    r.started_points(),
+                    r.optimal_points(),
+                    per_dollar(r.started_points_per_dollar()),
+                    per_dollar(r.optimal_points_per_dollar())
+                );
+                if let Some(best) = r.best_pickup() {
+                    line += &format!(
+                        "; best pickup {} (${}, {:.2} started)",
+                        player_name(&players, &best.player_id),
+                        best.bid,
+                        best.started_points
+                    );
+                }
+                if let Some(worst) = r.worst_pickup().filter(|w| Some(*w) != r.best_pickup()) {
+                    line += &format!(
+                        "; worst pickup {} (${}, {:.2} started)",
+                        player_name(&players, &worst.player_id),
+                        worst.bid,
+                        worst.started_points
+                    );
+                }
+                if let Some(drop) = r.costly_drops.first() {
+                    line += &format!(
+                        "; costliest drop {} (week {}, {:.2} started elsewhere)",
+                        player_name(&players, &drop.player_id),
+                        drop.week,
+                        drop.points_elsewhere
+                    );
+                }
+                line
+            })
+            .collect()
+    })
+}
+
+#[derive(Args, Debug)]
+pub struct DraftArgs {
+    #[arg(
+        long,
+        long_help = "Flag to grade each roster's picks by the points they scored through --week",
+        default_value = "false"
+    )]
+    grades: bool,
+}
+
+pub async fn draft(
+    client: &SleeperClient,
+    settings: &Settings,
+    args: DraftArgs,
+) -> Result<(), String> {
+    if args.grades {
+        return draft_grades(client, settings).await;
+    }
+    let league_id = settings.league_id()?;
+    let league = client.get_league_details(league_id.clone()).await?;
+    let picks = client.get_draft_picks(league.draft_id).await?;
+    let labels = settings.labels(&client.get_users_in_league(league_id).await?);
+    let players = client.fetch_all_players().await?;
+    emit(settings.format, &picks, |picks| {
+        picks
+            .iter()
+            .map(|p| {
+                let roster_id = p
+                    .roster_id
+                    .as_ref()
+                    .and_then(|r| r.parse().ok())
+                    .unwrap_or_default();
+                format!(
+                    "Round {} Pick {}: {} by {}{}",
+                    p.round,
+                    p.pick_no,
+                    player_name(&players, &p.player_id),
+                    labels.label(roster_id, &p.picked_by),
+                    if p.is_keeper.unwrap_or(false) {
+                        " (keeper)"
+                    } else {
+                        ""
+                    }
+                )
+            })
+            .collect()
+    })
+}
+
+async fn draft_grades(client: &SleeperClient, settings: &Settings) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let league = client.get_league_details(league_id.clone()).await?;
+    let rosters = client.get_rosters_in_league(league_id.clone()).await?;
+    let labels = settings.labels(&client.get_users_in_league(league_id).await?);
+    let picks = client.get_draft_picks(league.draft_id.clone()).await?;
+    let players = client.fetch_all_players().await?;
+    let weeks = season_matchups(client, settings).await?;
+    let usage = PlayerUsage::calculate(&weeks, &players, &league.roster_positions);
+    let mut grades = calculate_draft_grades(&picks, &usage, &league.roster_positions);
+    grades.retain(|g| !settings.is_excluded(g.roster_id));
+    emit(settings.format, &grades, |grades| {
+        let describe = |pick: &DraftPickValue| {
+            format!(
+                "{} (pick {}, finished {})",
+                player_name(&players, &pick.player_id),
+                pick.pick_no,
+                pick.finish
+            )
+        };
+        grades
+            .iter()
+            .map(|g| {
+                let rounds = g
+                    .value_by_round()
+                    .iter()
+                    .map(|(round, value)| format!("R{} {:.2}", round, value))
+                    .collect::<Vec<_>>();
+                let mut line = format!(
+                    "Draft Grade {}: {} started {:.2} ({:.2} optimal), {:.2} over replacement [{}]",
+                    g.grade,
+                    roster_label(&labels, &rosters, g.roster_id),
+                    g.started_points(),
+                    g.optimal_points(),
+                    g.value_over_replacement(),
+                    rounds.join(", ")
+                );
+                if let Some(steal) = g.steal() {
+                    line += &format!("; steal {}", describe(steal));
+                }
+                if let Some(bust) = g.bust() {
+                    line += &format!("; bust {}", describe(bust));
+                }
+                let keepers = g
+                    .keepers()
+                    .map(|k| {
+                        format!(
+                            "{} {:.2}",
+                            player_name(&players, &k.player_id),
+                            k.optimal_points
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                if !keepers.is_empty() {
+                    line += &format!("; keepers {}", keepers.join(", "));
+                }
+                line
+            })
+            .collect()
+    })
+}
+
+pub async fn bracket(
+    client: &SleeperClient,
+    settings: &Settings,
+    args: BracketArgs,
+) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let winner_or_loser = if args.losers {
+        WinnerOrLoser::Loser
+    } else {
+        WinnerOrLoser::Winner
+    };
+    let bracket = client
+        .get_playoff_bracket_for_league(league_id, winner_or_loser)
+        .await?;
+    emit(settings.format, &bracket, |bracket| {
+        bracket
+            .iter()
+            .map(|p| {
+                format!(
+                    "Round {} Matchup {}: {:?} vs {:?}, winner {:?}",
+                    p.round,
+                    p.matchup_id,
+                    p.roster_id_1_or_winner_matchup,
+                    p.roster_id_2_or_loser_of_matchup_id,
+                    p.winner
+                )
+            })
+            .collect()
+    })
+}
+
+pub async fn recap(
+    client: &SleeperClient,
+    settings: &Settings,
+    args: RecapArgs,
+) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let league = client.get_league_details(league_id.clone()).await?;
+    let mut matchups = client
+        .get_league_matchups_for_week(league_id.clone(), settings.week)
+        .await?;
+    matchups.retain(|m| !settings.is_excluded(m.roster_id));
+    let rosters = client.get_rosters_in_league(league_id.clone()).await?;
+    let labels = settings.labels(&client.get_users_in_league(league_id).await?);
+    let players = client.fetch_all_players().await?;
+    let recap = calculate_weekly_recap(
+        settings.week,
+        &matchups,
+        &rosters,
+        &players,
+        &league.roster_positions,
+        &labels,
+    )?;
+    let template = match &args.template {
+        Some(path) => Some(
+            std::fs::read_to_string(path)
+                .map_err(|e| format!("Unable to read template {}: {}", path, e))?,
+        ),
+        None => None,
+    };
+    let format = if args.html {
+        RecapFormat::Html
+    } else {
+        RecapFormat::Markdown
+    };
+    let rendered = render_recap(&recap, template.as_deref(), format)?;
+    emit(settings.format, &recap, |_| vec![rendered])
+}
+
+pub async fn dashboard(
+    client: &SleeperClient,
+    settings: &Settings,
+    args: DashboardArgs,
+) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let league = client.get_league_details(league_id.clone()).await?;
+    let rosters = client.get_rosters_in_league(league_id.clone()).await?;
+    let labels = settings.labels(&client.get_users_in_league(league_id.clone()).await?);
+    let players = client.fetch_all_players().await?;
+    let history = match &args.history_db {
+        Some(path) => Some(HistoryStore::open(path)?),
+        None => None,
+    };
+    let mut reports = vec![];
+    for week in 1..=settings.week {
+        let matchups = client
+            .get_league_matchups_for_week(league_id.clone(), week)
+            .await?;
+        reports.extend(calculate_bench_king_for_week(
+            week,
+            matchups,
+            &rosters_for_week(history.as_ref(), &league_id, week, &rosters)?,
+            &players,
+            &league,
+            None,
+        )?);
+    }
+    reports.retain(|r| !settings.is_excluded(r.roster_id));
+    let pages = render_dashboard(&league.name, &settings.season, &reports, &labels)?;
+    write_dashboard(&args.out_dir, &pages)?;
+    println!("Wrote {} pages to {}", pages.len(), args.out_dir);
+    Ok(())
+}
+
+pub async fn league(
+    client: &SleeperClient,
+    settings: &Settings,
+    command: LeagueCommand,
+) -> Result<(), String> {
+    match command {
+        LeagueCommand::Info => {
+            let league = client.get_league_details(settings.league_id()?).await?;
+            emit(settings.format, &league, |league| {
+                vec![
+                    format!("{} ({} {})", league.name, league.sport, league.season),
+                    format!("Status: {}", league.status),
+                    format!("Teams: {}", league.total_rosters),
+                    format!("Roster positions: {:?}", league.roster_positions),
+                    format!("Best ball: {}", league.is_best_ball()),
+                    format!("Playoffs start week {}", league.settings.playoff_week_start),
+                ]
+            })
+        }
+    }
+}
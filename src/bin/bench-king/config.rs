@@ -0,0 +1,226 @@
+//! Config file support.
+//!
+//! The config file is TOML, read from `--config` (or `BENCH_KING_CONFIG`) when
+//! given, otherwise from `$XDG_CONFIG_HOME/bench-king/config.toml`, falling back
+//! to `~/.config/bench-king/config.toml`. A missing default config file is not
+//! an error.
+//!
+//! ```toml
+//! default_league = "main"
+//! cache_dir = "/home/me/.cache/bench-king"
+//!
+//! [leagues.main]
+//! league_id = "1124926301107884032"
+//! aliases = ["work"]
+//! season = "2024"
+//! format = "text"
+//! team_names = true
+//! exclude_rosters = [12]
+//! ```
+//!
+//! Each setting is resolved in this order, first match wins:
+//! 1. command line flags
+//! 2. environment variables
+//! 3. the league profile selected by `--league-id` (a profile name, alias or
+//!    league id), or `default_league` when no league was given
+//! 4. top level config file settings
+//! 5. built-in defaults
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use bench_king_sleeper::calculation_helpers::report::OwnerLabels;
+use bench_king_sleeper::client::Sport;
+use bench_king_sleeper::models::{roster::RosterId, user::LeagueUser};
+use serde::Deserialize;
+
+use crate::output::OutputFormat;
+use crate::GlobalArgs;
+
+const DEFAULT_SEASON: &str = "2024";
+const DEFAULT_WEEK: i32 = 1;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    /// Profile name or alias used when no league is given
+    pub default_league: Option<String>,
+    pub cache_dir: Option<String>,
+    #[serde(default)]
+    pub leagues: HashMap<String, LeagueProfile>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct LeagueProfile {
+    pub league_id: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub season: Option<String>,
+    pub format: Option<OutputFormat>,
+    /// Label managers by team name instead of display name
+    pub team_names: Option<bool>,
+    /// Rosters left out of reports, e.g. a commissioner's placeholder team
+    #[serde(default)]
+    pub exclude_rosters: Vec<RosterId>,
+}
+
+impl Config {
+    pub fn default_path() -> Option<PathBuf> {
+        let config_home = match std::env::var("XDG_CONFIG_HOME") {
+            Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(std::env::var("HOME").ok()?).join(".config"),
+        };
+        Some(config_home.join("bench-king").join("config.toml"))
+    }
+
+    /// Loads the config at `path`, or the default path when none is given.
+    pub fn load(path: Option<&str>) -> Result<Config, String> {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => match Config::default_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Config::default()),
+            },
+        };
+        let data = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Unable to read config {}: {}", path.display(), e))?;
+        Config::parse(&data)
+    }
+
+    pub fn parse(data: &str) -> Result<Config, String> {
+        toml::from_str(data).map_err(|e| e.to_string())
+    }
+
+    /// Finds a profile by name, alias or league id.
+    pub fn profile(&self, league: &str) -> Option<&LeagueProfile> {
+        self.leagues.get(league).or_else(|| {
+            self.leagues
+                .values()
+                .find(|p| p.league_id == league || p.aliases.iter().any(|a| a == league))
+        })
+    }
+}
+
+/// Global options after merging flags, environment and config file.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub league_id: Option<String>,
+    pub season: String,
+    pub week: i32,
+    pub sport: String,
+    pub format: OutputFormat,
+    pub cache_dir: Option<String>,
+    pub team_names: bool,
+    pub excluded_rosters: Vec<RosterId>,
+}
+
+impl Settings {
+    pub fn resolve(global: &GlobalArgs, config: &Config) -> Result<Settings, String> {
+        let league = global.league_id.clone().or(config.default_league.clone());
+        let profile = league.as_ref().and_then(|l| config.profile(l));
+        if let (Some(league), None, None) = (&league, profile, &global.league_id) {
+            return Err(format!(
+                "default_league {} is not a league in the config",
+                league
+            ));
+        }
+        Ok(Settings {
+            league_id: profile.map(|p| p.league_id.clone()).or(league),
+            season: global
+                .season
+                .clone()
+                .or(profile.and_then(|p| p.season.clone()))
+                .unwrap_or(DEFAULT_SEASON.to_string()),
+            week: global.week.unwrap_or(DEFAULT_WEEK),
+            sport: global.sport.clone(),
+            format: global
+                .format
+                .or(profile.and_then(|p| p.format))
+                .unwrap_or(OutputFormat::Text),
+            cache_dir: global.cache_dir.clone().or(config.cache_dir.clone()),
+            team_names: global
+                .team_names
+                .or(profile.and_then(|p| p.team_names))
+                .unwrap_or(false),
+            excluded_rosters: profile
+                .map(|p| p.exclude_rosters.clone())
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn league_id(&self) -> Result<String, String> {
+        self.league_id.clone().ok_or(
+            "--league-id, SLEEPER_LEAGUE_ID or a default_league in the config is required for this command"
+                .to_string(),
+        )
+    }
+
+    pub fn sport(&self) -> Option<Sport> {
+        Some(Sport(self.sport.clone()))
+    }
+
+    pub fn labels(&self, owners: &[LeagueUser]) -> OwnerLabels {
+        if self.team_names {
+            OwnerLabels::with_team_names(owners)
+        } else {
+            OwnerLabels::new(owners)
+        }
+    }
+
+    pub fn is_excluded(&self, roster_id: RosterId) -> bool {
+        self.excluded_rosters.contains(&roster_id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::*;
+    use crate::Cli;
+
+    const CONFIG: &str = r#"
+        default_league = "main"
+
+        [leagues.main]
+        league_id = "111"
+        format = "json"
+        exclude_rosters = [12]
+        team_names = true
+
+        [leagues.dynasty]
+        league_id = "222"
+        aliases = ["dyn"]
+        season = "2023"
+    "#;
+
+    fn resolve(args: &[&str]) -> Settings {
+        let cli = Cli::parse_from([&["bench-king"], args, &["standings"]].concat());
+        Settings::resolve(&cli.global, &Config::parse(CONFIG).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn default_league_profile() {
+        let settings = resolve(&[]);
+        assert_eq!(settings.league_id.as_deref(), Some("111"));
+        assert_eq!(settings.format, OutputFormat::Json);
+        assert!(settings.is_excluded(12));
+        assert!(settings.team_names);
+    }
+
+    #[test]
+    fn flags_override_profile() {
+        let settings = resolve(&["--league-id", "dyn", "--season", "2024", "--format", "text"]);
+        assert_eq!(settings.league_id.as_deref(), Some("222"));
+        assert_eq!(settings.season, "2024");
+        assert_eq!(settings.format, OutputFormat::Text);
+        assert!(!settings.is_excluded(12));
+
+        let settings = resolve(&["--league-id", "333"]);
+        assert_eq!(settings.league_id.as_deref(), Some("333"));
+        assert_eq!(settings.season, DEFAULT_SEASON);
+        assert!(!settings.team_names);
+
+        assert!(!resolve(&["--team-names=false"]).team_names);
+        assert!(resolve(&["--league-id", "dyn", "--team-names"]).team_names);
+    }
+}
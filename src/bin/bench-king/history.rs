@@ -0,0 +1,231 @@
+use bench_king_sleeper::calculation_helpers::calculate_bench_king_for_week::calculate_bench_king_for_week;
+use bench_king_sleeper::calculation_helpers::calculate_rosters_at_week::calculate_rosters_at_week;
+use bench_king_sleeper::calculation_helpers::report::{round_to_two_decimals, OwnerLabels, Report};
+use bench_king_sleeper::client::SleeperClient;
+use bench_king_sleeper::history::{HistoryStore, WeekSnapshot};
+use bench_king_sleeper::models::{matchup::Matchup, roster::Roster, transaction::Transaction};
+use clap::{Args, Subcommand};
+
+use crate::commands::LAST_WEEK;
+use crate::config::Settings;
+use crate::output::emit;
+
+#[derive(Args, Debug)]
+pub struct HistoryArgs {
+    #[arg(
+        long,
+        env = "BENCH_KING_HISTORY_DB",
+        long_help = "SQLite database of saved weeks",
+        default_value = "bench-king-history.sqlite"
+    )]
+    db: String,
+    #[command(subcommand)]
+    command: HistoryCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum HistoryCommand {
+    /// Save --week once it has just finished
+    Save {
+        #[arg(
+            long,
+            long_help = "Flag to save --week even if it isn't the week that just finished. Earlier weeks' rosters are rebuilt from their matchups and transactions",
+            default_value = "false"
+        )]
+        force: bool,
+    },
+    /// Bench king for a saved --week, without calling the Sleeper API
+    Week,
+    /// Every saved week for a roster
+    Roster { roster_id: u32 },
+    /// Every saved season for an owner, across leagues
+    Owner { owner_id: String },
+}
+
+pub async fn run(
+    client: &SleeperClient,
+    settings: &Settings,
+    args: HistoryArgs,
+) -> Result<(), String> {
+    let mut store = HistoryStore::open(&args.db)?;
+    match args.command {
+        HistoryCommand::Save { force } => save(client, settings, &mut store, force).await,
+        HistoryCommand::Week => {
+            let league_id = settings.league_id()?;
+            let snapshot = store
+                .load_week(&league_id, settings.week)?
+                .ok_or(format!("Week {} hasn't been saved", settings.week))?;
+            let labels = settings.labels(&snapshot.users);
+            let mut reports = snapshot.reports;
+            reports.retain(|r| !settings.is_excluded(r.roster_id));
+            reports.sort_by(|a, b| b.difference().partial_cmp(&a.difference()).unwrap());
+            emit(settings.format, &reports, |reports| {
+                reports
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, r)| {
+                        format!("Bench King Rank: {}: {}", idx + 1, r.describe(&labels))
+                    })
+                    .collect()
+            })
+        }
+        HistoryCommand::Roster { roster_id } => {
+            let league_id = settings.league_id()?;
+            let reports = store.weeks_for_roster(&league_id, roster_id)?;
+            let labels = saved_labels(&store, settings, &league_id)?;
+            emit(settings.format, &reports, |reports| {
+                reports
+                    .iter()
+                    .map(|r| format!("Week {}: {}", r.week, r.describe(&labels)))
+                    .collect()
+            })
+        }
+        HistoryCommand::Owner { owner_id } => {
+            let seasons = store.seasons_for_owner(&owner_id)?;
+            emit(settings.format, &seasons, |seasons| {
+                seasons
+                    .iter()
+                    .map(|s| {
+                        let actual = s.reports.iter().map(|r| r.actual_points).sum::<f32>();
+                        let optimal = s.reports.iter().map(|r| r.optimal_points).sum::<f32>();
+                        let total = Report {
+                            actual_points: actual,
+                            optimal_points: optimal,
+                            ..Default::default()
+                        };
+                        format!(
+                            "{} {}: {} weeks, left {} points on the bench, efficiency is {}%",
+                            s.season,
+                            s.league_name,
+                            s.reports.len(),
+                            round_to_two_decimals(total.difference()),
+                            round_to_two_decimals(total.efficiency() * 100.0)
+                        )
+                    })
+                    .collect()
+            })
+        }
+    }
+}
+
+/// Labels from the most recently saved week, so lookups need no API calls.
+fn saved_labels(
+    store: &HistoryStore,
+    settings: &Settings,
+    league_id: &str,
+) -> Result<OwnerLabels, String> {
+    let Some(week) = store.saved_weeks(league_id)?.pop() else {
+        return Ok(OwnerLabels::default());
+    };
+    Ok(store
+        .load_week(league_id, week)?
+        .map(|s| settings.labels(&s.users))
+        .unwrap_or_default())
+}
+
+async fn save(
+    client: &SleeperClient,
+    settings: &Settings,
+    store: &mut HistoryStore,
+    force: bool,
+) -> Result<(), String> {
+    let league_id = settings.league_id()?;
+    let week = settings.week;
+    let league = client.get_league_details(league_id.clone()).await?;
+    let state = client.get_state(settings.sport()).await?;
+    // Today's rosters are only the week's rosters until the next week's moves
+    let just_finished = league.season == state.season && week == state.week as i32 - 1;
+    if !force && !just_finished {
+        return Err(format!(
+            "Week {} isn't the week that just finished, use --force to save it anyway",
+            week
+        ));
+    }
+    let rosters = client.get_rosters_in_league(league_id.clone()).await?;
+    let users = client.get_users_in_league(league_id.clone()).await?;
+    let matchups = client
+        .get_league_matchups_for_week(league_id.clone(), week)
+        .await?;
+    let rosters = if just_finished {
+        rosters
+    } else {
+        let transactions = client
+            .get_transactions_through(league_id, LAST_WEEK)
+            .await?;
+        rosters_at_week(week, &matchups, &rosters, &transactions)
+    };
+    let players = client.fetch_all_players().await?;
+    let reports =
+        calculate_bench_king_for_week(week, matchups.clone(), &rosters, &players, &league, None)?;
+    store.save_week(&WeekSnapshot {
+        league,
+        week,
+        rosters,
+        matchups,
+        users,
+        reports,
+    })?;
+    println!("Saved week {}", week);
+    Ok(())
+}
+
+/// Today's rosters with the players they had in `week`. Sleeper doesn't record
+/// ownership changes, so the owners are still the current ones.
+fn rosters_at_week(
+    week: i32,
+    matchups: &[Matchup],
+    current: &[Roster],
+    transactions: &[Transaction],
+) -> Vec<Roster> {
+    calculate_rosters_at_week(week, matchups, current, transactions)
+        .into_iter()
+        .zip(current)
+        .map(|(at_week, roster)| Roster {
+            starters: at_week.starters,
+            players: Some(at_week.players),
+            ..roster.clone()
+        })
+        .collect()
+}
+
+/// Rosters with the owners they had in `week`. Sleeper only returns current
+/// owners, so past weeks come from the history database when they were saved.
+pub fn rosters_for_week(
+    history: Option<&HistoryStore>,
+    league_id: &str,
+    week: i32,
+    current: &[Roster],
+) -> Result<Vec<Roster>, String> {
+    let saved = match history {
+        Some(store) => store.rosters_for_week(league_id, week)?,
+        None => None,
+    };
+    Ok(saved.unwrap_or_else(|| current.to_vec()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn backfilled_weeks_get_that_weeks_players() {
+        let rosters: Vec<Roster> =
+            serde_json::from_str(include_str!("../../../fixtures/rosters.json")).unwrap();
+        let transactions: Vec<Transaction> =
+            serde_json::from_str(include_str!("../../../fixtures/transactions.json")).unwrap();
+        let week_one: Vec<Matchup> =
+            serde_json::from_str(include_str!("../../../fixtures/matchups_week_1.json")).unwrap();
+
+        // Roster 1 claimed 9999 for 8146 in week 2
+        for matchups in [week_one, vec![]] {
+            let backfilled = rosters_at_week(1, &matchups, &rosters, &transactions);
+            assert_eq!(backfilled.len(), 2);
+            assert!(backfilled[0].has_player("8146"));
+            assert!(!backfilled[0].has_player("9999"));
+            assert_eq!(backfilled[0].owner_id, rosters[0].owner_id);
+            // Roster 2's claim failed, so it keeps 7564
+            assert!(backfilled[1].has_player("7564"));
+        }
+        assert!(rosters[0].has_player("9999"));
+    }
+}
@@ -0,0 +1,179 @@
+mod bench_king;
+mod commands;
+mod config;
+mod history;
+mod output;
+mod serve;
+
+use bench_king_sleeper::client::SleeperClient;
+use clap::{Args, Parser, Subcommand};
+use config::{Config, Settings};
+use output::OutputFormat;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "bench-king",
+    about = "Fantasy football analytics for Sleeper leagues"
+)]
+struct Cli {
+    #[command(flatten)]
+    global: GlobalArgs,
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Options shared by every subcommand. Unset options fall back to the config
+/// file, see the `config` module for the precedence rules.
+#[derive(Args, Debug, Clone)]
+pub struct GlobalArgs {
+    #[arg(
+        long,
+        global = true,
+        env = "BENCH_KING_CONFIG",
+        long_help = "Config file, defaults to $XDG_CONFIG_HOME/bench-king/config.toml. Flags and environment variables override the config file"
+    )]
+    pub config: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        env = "SLEEPER_LEAGUE_ID",
+        long_help = "The Sleeper league ID, or the name or alias of a league in the config file"
+    )]
+    pub league_id: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        env = "SLEEPER_SEASON",
+        long_help = "Season to query [default: 2024]"
+    )]
+    pub season: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        long_help = "Week to query, season reports cover weeks 1 through this week [default: 1]"
+    )]
+    pub week: Option<i32>,
+    #[arg(
+        long,
+        short,
+        global = true,
+        long_help = "Sport to query",
+        default_value = "nfl"
+    )]
+    pub sport: String,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        env = "BENCH_KING_FORMAT",
+        long_help = "Output format [default: text]"
+    )]
+    pub format: Option<OutputFormat>,
+    #[arg(
+        long,
+        global = true,
+        env = "BENCH_KING_CACHE_DIR",
+        long_help = "Directory for the HTTP response cache"
+    )]
+    pub cache_dir: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        long_help = "Label managers by team name instead of display name, --team-names=false overrides the config file [default: false]"
+    )]
+    pub team_names: Option<bool>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Rank managers by points left on the bench
+    BenchKing(bench_king::BenchKingArgs),
+    /// League standings
+    Standings,
+    /// Head-to-head matchups for the week
+    Matchups,
+    /// Rosters and their players
+    Rosters(commands::RostersArgs),
+    /// Player lookups
+    Players {
+        #[command(subcommand)]
+        command: commands::PlayersCommand,
+    },
+    /// Most added or dropped players
+    Trending(commands::TrendingArgs),
+    /// Unrostered players the league's starting slots can use
+    FreeAgents(commands::FreeAgentsArgs),
+    /// Best lineup that could have been started from unrostered players for --week
+    WaiverWireKing,
+    /// Bench regret for --week split into bad decisions against projections and bad luck
+    DecisionQuality,
+    /// Transactions for the week
+    Transactions,
+    /// Completed trades judged by the points each side started since
+    Trades,
+    /// FAAB spent and points returned from waiver and free agent pickups
+    Waivers,
+    /// Picks from the league's draft
+    Draft(commands::DraftArgs),
+    /// Playoff bracket
+    Bracket(commands::BracketArgs),
+    /// Weekly recap rendered from a Markdown or HTML template
+    Recap(commands::RecapArgs),
+    /// Write a static HTML dashboard for the season through --week
+    Dashboard(commands::DashboardArgs),
+    /// Save final weeks to a local database and report on them offline
+    History(history::HistoryArgs),
+    /// Serve league analytics as a JSON HTTP API
+    Serve(serve::ServeArgs),
+    /// League details
+    League {
+        #[command(subcommand)]
+        command: commands::LeagueCommand,
+    },
+}
+
+#[tokio::main]
+pub async fn main() {
+    let cli = Cli::parse();
+    let settings = match Config::load(cli.global.config.as_deref())
+        .and_then(|config| Settings::resolve(&cli.global, &config))
+    {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let client = match &settings.cache_dir {
+        Some(cache_dir) => SleeperClient::build_with_cache_dir(cache_dir),
+        None => SleeperClient::build(),
+    };
+    let result = match cli.command {
+        Command::BenchKing(args) => bench_king::run(&client, &settings, args).await,
+        Command::Standings => commands::standings(&client, &settings).await,
+        Command::Matchups => commands::matchups(&client, &settings).await,
+        Command::Rosters(args) => commands::rosters(&client, &settings, args).await,
+        Command::Players { command } => commands::players(&client, &settings, command).await,
+        Command::Trending(args) => commands::trending(&client, &settings, args).await,
+        Command::FreeAgents(args) => commands::free_agents(&client, &settings, args).await,
+        Command::WaiverWireKing => commands::waiver_wire_king(&client, &settings).await,
+        Command::DecisionQuality => commands::decision_quality(&client, &settings).await,
+        Command::Transactions => commands::transactions(&client, &settings).await,
+        Command::Trades => commands::trades(&client, &settings).await,
+        Command::Waivers => commands::waivers(&client, &settings).await,
+        Command::Draft(args) => commands::draft(&client, &settings, args).await,
+        Command::Bracket(args) => commands::bracket(&client, &settings, args).await,
+        Command::Recap(args) => commands::recap(&client, &settings, args).await,
+        Command::Dashboard(args) => commands::dashboard(&client, &settings, args).await,
+        Command::History(args) => history::run(&client, &settings, args).await,
+        Command::Serve(args) => serve::run(client, settings, args).await,
+        Command::League { command } => commands::league(&client, &settings, command).await,
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,31 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(ValueEnum, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// Prints `data` as pretty JSON, or the lines from `text` for text output.
+pub fn emit<T, F>(format: OutputFormat, data: &T, text: F) -> Result<(), String>
+where
+    T: Serialize,
+    F: FnOnce(&T) -> Vec<String>,
+{
+    match format {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(data).map_err(|e| e.to_string())?
+            );
+        }
+        OutputFormat::Text => {
+            for line in text(data) {
+                println!("{}", line);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,439 @@
+//! `bench-king serve`, a small JSON API over the same calculations as the CLI.
+//!
+//! ```text
+//! GET /leagues/{id}/bench-king?week=3
+//! GET /leagues/{id}/standings
+//! GET /leagues/{id}/season?week=10
+//! ```
+//!
+//! `week` defaults to `--week`. Sleeper responses go through the client's HTTP
+//! cache, the player list is kept in memory for a day and each league week's
+//! reports for a few minutes, so scores still update during games.
+//!
+//! Unknown leagues are a 404 and bad `week` values a 400. Errors from Sleeper
+//! or from calculating its data are a 502.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use bench_king_sleeper::calculation_helpers::calculate_bench_king_for_week::calculate_bench_king_for_week;
+use bench_king_sleeper::calculation_helpers::calculate_efficiency::{
+    calculate_efficiency_leaderboard, SeasonSummary,
+};
+use bench_king_sleeper::calculation_helpers::report::Report;
+use bench_king_sleeper::client::SleeperClient;
+use bench_king_sleeper::models::{league::League, player::Players, roster::Roster};
+use clap::Args;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::{standing_rows, StandingRow, LAST_WEEK};
+use crate::config::Settings;
+
+const PLAYERS_TTL: Duration = Duration::from_secs(86400);
+const REPORTS_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    #[arg(
+        long,
+        env = "BENCH_KING_ADDR",
+        long_help = "Address to listen on",
+        default_value = "127.0.0.1:8080"
+    )]
+    addr: String,
+}
+
+/// A league week's reports and when they were calculated
+type CachedReports = (Instant, Arc<Vec<Report>>);
+
+struct AppState {
+    client: SleeperClient,
+    settings: Settings,
+    players: Mutex<Option<(Instant, Arc<Players>)>>,
+    /// Unfiltered bench king reports by league id and week
+    reports: Mutex<HashMap<(String, i32), CachedReports>>,
+}
+
+impl AppState {
+    fn new(client: SleeperClient, settings: Settings) -> AppState {
+        AppState {
+            client,
+            settings,
+            players: Mutex::new(None),
+            reports: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn players(&self) -> Result<Arc<Players>, String> {
+        if let Some((fetched, players)) = self.players.lock().unwrap().as_ref() {
+            if fetched.elapsed() < PLAYERS_TTL {
+                return Ok(players.clone());
+            }
+        }
+        let players = Arc::new(self.client.fetch_all_players().await?);
+        *self.players.lock().unwrap() = Some((Instant::now(), players.clone()));
+        Ok(players)
+    }
+
+    async fn league(&self, league_id: &str) -> Result<Arc<League>, ApiError> {
+        match self.client.find_league(league_id.to_string()).await? {
+            Some(league) => Ok(Arc::new(league)),
+            None => Err(ApiError::NotFound(format!(
+                "League {} not found",
+                league_id
+            ))),
+        }
+    }
+
+    /// Bench king reports for a league week. Optimal lineups are CPU-bound,
+    /// so they run on the blocking pool.
+    async fn week_reports(
+        &self,
+        league: &Arc<League>,
+        rosters: &Arc<Vec<Roster>>,
+        players: &Arc<Players>,
+        week: i32,
+    ) -> Result<Arc<Vec<Report>>, ApiError> {
+        let key = (league.league_id.clone(), week);
+        if let Some((calculated, reports)) = self.reports.lock().unwrap().get(&key) {
+            if calculated.elapsed() < REPORTS_TTL {
+                return Ok(reports.clone());
+            }
+        }
+        let matchups = self
+            .client
+            .get_league_matchups_for_week(league.league_id.clone(), week)
+            .await?;
+        let (league, rosters, players) = (league.clone(), rosters.clone(), players.clone());
+        let reports = tokio::task::spawn_blocking(move || {
+            calculate_bench_king_for_week(week, matchups, &rosters, &players, &league, None)
+        })
+        .await
+        .map_err(|e| e.to_string())??;
+        let reports = Arc::new(reports);
+        self.reports
+            .lock()
+            .unwrap()
+            .insert(key, (Instant::now(), reports.clone()));
+        Ok(reports)
+    }
+
+    /// Settings for `league_id`; roster exclusions only apply to the configured league.
+    fn settings_for(&self, league_id: &str) -> Settings {
+        let mut settings = self.settings.clone();
+        if settings.league_id.as_deref() != Some(league_id) {
+            settings.excluded_rosters.clear();
+        }
+        settings
+    }
+}
+
+enum ApiError {
+    BadRequest(String),
+    NotFound(String),
+    /// Sleeper failed, or sent data the calculations couldn't use
+    Upstream(String),
+}
+
+impl From<String> for ApiError {
+    fn from(e: String) -> Self {
+        ApiError::Upstream(e)
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        ApiError::BadRequest(rejection.body_text())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        #[derive(Serialize)]
+        struct Body {
+            error: String,
+        }
+        let (status, error) = match self {
+            ApiError::BadRequest(e) => (StatusCode::BAD_REQUEST, e),
+            ApiError::NotFound(e) => (StatusCode::NOT_FOUND, e),
+            ApiError::Upstream(e) => (StatusCode::BAD_GATEWAY, e),
+        };
+        (status, Json(Body { error })).into_response()
+    }
+}
+
+#[derive(Deserialize)]
+struct WeekQuery {
+    week: Option<i32>,
+}
+
+/// The requested week, or `--week` when none was given.
+fn requested_week(
+    query: Result<Query<WeekQuery>, QueryRejection>,
+    settings: &Settings,
+) -> Result<i32, ApiError> {
+    let week = query?.week.unwrap_or(settings.week);
+    if !(1..=LAST_WEEK).contains(&week) {
+        return Err(ApiError::BadRequest(format!(
+            "week must be between 1 and {}, got {}",
+            LAST_WEEK, week
+        )));
+    }
+    Ok(week)
+}
+
+#[derive(Serialize)]
+struct LabelledReport {
+    owner: String,
+    #[serde(flatten)]
+    report: Report,
+}
+
+#[derive(Serialize)]
+struct SeasonRow {
+    owner: String,
+    actual_points: f32,
+    optimal_points: f32,
+    difference: f32,
+    efficiency: f32,
+    #[serde(flatten)]
+    summary: SeasonSummary,
+}
+
+fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/leagues/{id}/bench-king", get(bench_king))
+        .route("/leagues/{id}/standings", get(standings))
+        .route("/leagues/{id}/season", get(season))
+        .with_state(state)
+}
+
+pub async fn run(client: SleeperClient, settings: Settings, args: ServeArgs) -> Result<(), String> {
+    let app = router(Arc::new(AppState::new(client, settings)));
+    let listener = tokio::net::TcpListener::bind(&args.addr)
+        .await
+        .map_err(|e| format!("Unable to listen on {}: {}", args.addr, e))?;
+    eprintln!("Listening on http://{}", args.addr);
+    axum::serve(listener, app).await.map_err(|e| e.to_string())
+}
+
+async fn bench_king(
+    State(state): State<Arc<AppState>>,
+    Path(league_id): Path<String>,
+    query: Result<Query<WeekQuery>, QueryRejection>,
+) -> Result<Json<Vec<LabelledReport>>, ApiError> {
+    let settings = state.settings_for(&league_id);
+    let week = requested_week(query, &settings)?;
+    let client = &state.client;
+    let league = state.league(&league_id).await?;
+    let rosters = Arc::new(client.get_rosters_in_league(league_id.clone()).await?);
+    let labels = settings.labels(&client.get_users_in_league(league_id).await?);
+    let players = state.players().await?;
+    let mut reports = state
+        .week_reports(&league, &rosters, &players, week)
+        .await?
+        .to_vec();
+    reports.retain(|r| !settings.is_excluded(r.roster_id));
+    reports.sort_by(|a, b| b.difference().partial_cmp(&a.difference()).unwrap());
+    Ok(Json(
+        reports
+            .into_iter()
+            .map(|report| LabelledReport {
+                owner: labels.label(report.roster_id, &report.owner_id),
+                report,
+            })
+            .collect(),
+    ))
+}
+
+async fn standings(
+    State(state): State<Arc<AppState>>,
+    Path(league_id): Path<String>,
+) -> Result<Json<Vec<StandingRow>>, ApiError> {
+    let settings = state.settings_for(&league_id);
+    state.league(&league_id).await?;
+    Ok(Json(
+        standing_rows(&state.client, &settings, league_id).await?,
+    ))
+}
+
+/// Season to date efficiency leaderboard, weeks 1 through `week`.
+async fn season(
+    State(state): State<Arc<AppState>>,
+    Path(league_id): Path<String>,
+    query: Result<Query<WeekQuery>, QueryRejection>,
+) -> Result<Json<Vec<SeasonRow>>, ApiError> {
+    let settings = state.settings_for(&league_id);
+    let week = requested_week(query, &settings)?;
+    let client = &state.client;
+    let league = state.league(&league_id).await?;
+    let rosters = Arc::new(client.get_rosters_in_league(league_id.clone()).await?);
+    let labels = settings.labels(&client.get_users_in_league(league_id).await?);
+    let players = state.players().await?;
+    let weeks =
+        join_all((1..=week).map(|week| state.week_reports(&league, &rosters, &players, week)))
+            .await;
+    let mut reports = vec![];
+    for week in weeks {
+        reports.extend(week?.iter().cloned());
+    }
+    reports.retain(|r| !settings.is_excluded(r.roster_id));
+    Ok(Json(
+        calculate_efficiency_leaderboard(reports)
+            .into_iter()
+            .map(|summary| SeasonRow {
+                owner: labels.label(summary.roster_id, &summary.owner_id()),
+                actual_points: summary.actual_points(),
+                optimal_points: summary.optimal_points(),
+                difference: summary.difference(),
+                efficiency: summary.efficiency(),
+                summary,
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::output::OutputFormat;
+
+    pub(crate) const LEAGUE_ID: &str = "1100000000000000000";
+    /// A league whose rosters are missing, so its matchups can't be calculated
+    const BROKEN_LEAGUE_ID: &str = "1100000000000000001";
+
+    pub(crate) async fn serve(app: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}/", addr)
+    }
+
+    /// Stands in for the Sleeper API with the recorded fixtures.
+    pub(crate) async fn sleeper_stand_in() -> String {
+        let fixture = |json: &'static str| move || async move { json };
+        serve(
+            Router::new()
+                .route(
+                    "/league/{id}",
+                    get(|Path(id): Path<String>| async move {
+                        let league = include_str!("../../../fixtures/league.json");
+                        match id.as_str() {
+                            LEAGUE_ID => league.to_string(),
+                            BROKEN_LEAGUE_ID => league.replace(LEAGUE_ID, BROKEN_LEAGUE_ID),
+                            _ => "null".to_string(),
+                        }
+                    }),
+                )
+                .route(
+                    "/league/{id}/rosters",
+                    get(|Path(id): Path<String>| async move {
+                        match id.as_str() {
+                            LEAGUE_ID => include_str!("../../../fixtures/rosters.json"),
+                            _ => "[]",
+                        }
+                    }),
+                )
+                .route(
+                    "/league/{id}/users",
+                    get(fixture(include_str!("../../../fixtures/users.json"))),
+                )
+                .route(
+                    "/league/{id}/matchups/{week}",
+                    get(|Path((_, week)): Path<(String, i32)>| async move {
+                        match week {
+                            1 => include_str!("../../../fixtures/matchups_week_1.json"),
+                            2 => include_str!("../../../fixtures/matchups_week_2.json"),
+                            _ => "[]",
+                        }
+                    }),
+                )
+                .route(
+                    "/players/nfl",
+                    get(fixture(include_str!("../../../fixtures/players.json"))),
+                ),
+        )
+        .await
+    }
+
+    async fn bench_king_api() -> String {
+        let cache_dir = std::env::temp_dir().join("bench-king-serve-test");
+        let client =
+            SleeperClient::build_with_cache_dir(cache_dir).with_base_url(sleeper_stand_in().await);
+        let settings = Settings {
+            league_id: Some(LEAGUE_ID.to_string()),
+            season: "2024".to_string(),
+            week: 1,
+            sport: "nfl".to_string(),
+            format: OutputFormat::Json,
+            cache_dir: None,
+            team_names: false,
+            excluded_rosters: vec![],
+        };
+        serve(router(Arc::new(AppState::new(client, settings)))).await
+    }
+
+    async fn get_json(url: String) -> (StatusCode, Value) {
+        let response = reqwest::get(url).await.unwrap();
+        let status = response.status();
+        (
+            status,
+            serde_json::from_str(&response.text().await.unwrap()).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn serves_bench_king_standings_and_season() {
+        let api = bench_king_api().await;
+
+        let (status, body) = get_json(format!("{api}leagues/{LEAGUE_ID}/bench-king?week=1")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body[0]["owner"], "alice");
+        assert_eq!(body[0]["roster_id"], 1);
+        assert_eq!(body[1]["owner"], "bob");
+
+        let (status, body) = get_json(format!("{api}leagues/{LEAGUE_ID}/standings")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.as_array().unwrap().len(), 2);
+        assert_eq!(body[0]["owner"], "alice");
+
+        let (status, body) = get_json(format!("{api}leagues/{LEAGUE_ID}/season?week=2")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.as_array().unwrap().len(), 2);
+        assert_eq!(body[0]["weeks"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn maps_errors_to_statuses() {
+        let api = bench_king_api().await;
+
+        for week in ["0", "19", "abc"] {
+            let (status, body) =
+                get_json(format!("{api}leagues/{LEAGUE_ID}/bench-king?week={week}")).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "week={}", week);
+            assert!(body["error"].is_string());
+        }
+        let (status, _) = get_json(format!("{api}leagues/{LEAGUE_ID}/season?week=-1")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        for path in ["bench-king", "standings", "season"] {
+            let (status, _) = get_json(format!("{api}leagues/404/{path}")).await;
+            assert_eq!(status, StatusCode::NOT_FOUND, "{}", path);
+        }
+
+        let (status, body) =
+            get_json(format!("{api}leagues/{BROKEN_LEAGUE_ID}/bench-king?week=1")).await;
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert_eq!(body["error"], "No roster 1 for week 1 matchup 1");
+    }
+}
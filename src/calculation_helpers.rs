@@ -1,3 +1,17 @@
+pub mod calculate_achievable_points;
+pub mod calculate_bench_decisions;
 pub mod calculate_bench_king_for_week;
+pub mod calculate_best_ball;
+pub mod calculate_decision_quality;
+pub mod calculate_draft_grades;
+pub mod calculate_efficiency;
+pub mod calculate_free_agents;
+pub mod calculate_matchup_outcomes;
 pub mod calculate_optimal_points;
+pub mod calculate_player_usage;
+pub mod calculate_rosters_at_week;
+pub mod calculate_trades;
+pub mod calculate_waiver_wire_king;
+pub mod calculate_waivers;
+pub mod calculate_weekly_recap;
 pub mod report;
@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+
+use super::calculate_optimal_points::{lineup_points, optimal_lineup};
+use crate::models::{
+    matchup::Matchup,
+    player::{PlayerDetails, Players},
+    positions::RosterPosition,
+    schedule::Schedule,
+};
+
+/// The best lineup a manager could still have reached, given that players
+/// are locked into (or out of) the lineup once their game kicks off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AchievableScoreForMatchup {
+    pub achievable_points: f32,
+    /// Players whose game kicked off before the decision cutoff
+    pub locked_players: Vec<String>,
+    pub achievable_starters: Vec<String>,
+}
+
+/// Whether a player's game had already kicked off at `cutoff`. Players without a
+/// team or game that week are never locked.
+pub fn is_locked(
+    player: Option<&PlayerDetails>,
+    schedule: &Schedule,
+    week: i32,
+    cutoff: DateTime<Utc>,
+) -> bool {
+    player
+        .and_then(|p| p.team.as_ref())
+        .and_then(|team| schedule.kickoff(week, team))
+        .map(|kickoff| kickoff < cutoff)
+        .unwrap_or(false)
+}
+
+/// Computes the achievable optimum for a matchup. Starters locked before
+/// `cutoff` keep their slot and bench players locked before `cutoff` can't be
+/// swapped in; every other slot is filled optimally from the unlocked players.
+///
+/// `Matchup::starters` is ordered like the non-bench `league_positions`, which
+/// is how locked starters are matched to the slot they occupy.
+pub fn achievable_score_for_matchup(
+    matchup: &Matchup,
+    players: &Players,
+    league_positions: &[RosterPosition],
+    schedule: &Schedule,
+    week: i32,
+    cutoff: DateTime<Utc>,
+) -> AchievableScoreForMatchup {
+    let locked = |player_id: &String| is_locked(players.get(player_id), schedule, week, cutoff);
+    let slots = league_positions
+        .iter()
+        .filter(|p| **p != RosterPosition::BN)
+        .collect::<Vec<_>>();
+
+    let mut achievable_starters = vec![];
+    let mut open_slots = vec![];
+    for (idx, slot) in slots.into_iter().enumerate() {
+        match matchup.starters.get(idx) {
+            Some(starter) if locked(starter) => achievable_starters.push(starter.clone()),
+            _ => open_slots.push(*slot),
+        }
+    }
+    let candidates = matchup
+        .players
+        .iter()
+        .filter(|p| !locked(p))
+        .filter_map(|p| players.get(p))
+        .collect::<Vec<_>>();
+    let lineup = optimal_lineup(&open_slots, &candidates, &matchup.players_points);
+    let frozen = achievable_starters
+        .iter()
+        .map(|p| matchup.players_points.get(p).unwrap_or(&0.0f32))
+        .sum::<f32>();
+
+    AchievableScoreForMatchup {
+        achievable_points: frozen + lineup_points(&lineup, &matchup.players_points),
+        locked_players: matchup
+            .players
+            .iter()
+            .filter(|p| locked(p))
+            .cloned()
+            .collect(),
+        achievable_starters: achievable_starters
+            .into_iter()
+            .chain(lineup.into_iter().map(|(_, player_id)| player_id))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn early_games_are_locked() {
+        let players: Players = serde_json::from_str(
+            r#"{
+                "thu": {"active": true, "player_id": "thu", "sport": "nfl", "team": "KC", "fantasy_positions": ["WR"]},
+                "lon": {"active": true, "player_id": "lon", "sport": "nfl", "team": "NYJ", "fantasy_positions": ["WR"]},
+                "sun": {"active": true, "player_id": "sun", "sport": "nfl", "team": "BUF", "fantasy_positions": ["WR"]},
+                "sun2": {"active": true, "player_id": "sun2", "sport": "nfl", "team": "ATL", "fantasy_positions": ["WR"]}
+            }"#,
+        )
+        .unwrap();
+        let schedule: Schedule = serde_json::from_str(
+            r#"[
+                { "week": 1, "home": "KC", "away": "BAL", "kickoff": "2024-09-06T00:20:00Z" },
+                { "week": 1, "home": "NYJ", "away": "MIN", "kickoff": "2024-09-08T13:30:00Z" },
+                { "week": 1, "home": "BUF", "away": "ARI", "kickoff": "2024-09-08T17:00:00Z" },
+                { "week": 1, "home": "ATL", "away": "PIT", "kickoff": "2024-09-08T17:00:00Z" }
+            ]"#,
+        )
+        .unwrap();
+        let matchup = Matchup {
+            starters: vec!["thu".to_string(), "sun".to_string()],
+            roster_id: 1,
+            players: vec!["thu", "lon", "sun", "sun2"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            matchup_id: 1,
+            points: 12.0,
+            custom_points: None,
+            players_points: [("thu", 2.0), ("lon", 30.0), ("sun", 10.0), ("sun2", 20.0)]
+                .into_iter()
+                .map(|(p, pts)| (p.to_string(), pts))
+                .collect(),
+            starters_points: vec![2.0, 10.0],
+        };
+        let cutoff = schedule.main_slate_kickoff(1).unwrap();
+        let score = achievable_score_for_matchup(
+            &matchup,
+            &players,
+            &[RosterPosition::WR, RosterPosition::WR, RosterPosition::BN],
+            &schedule,
+            1,
+            cutoff,
+        );
+        // Thursday starter stays, London bench player can't come in, Sunday swap is allowed
+        assert_eq!(score.achievable_points, 22.0);
+        assert_eq!(score.locked_players, vec!["thu", "lon"]);
+        assert_eq!(score.achievable_starters, vec!["thu", "sun2"]);
+    }
+}
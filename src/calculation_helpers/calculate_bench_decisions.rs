@@ -0,0 +1,215 @@
+use serde::Serialize;
+
+use super::calculate_optimal_points::optimal_lineup;
+use super::report::{round_to_two_decimals, OwnerLabels};
+use crate::models::{
+    injury::InjurySnapshot, matchup::Matchup, player::Players, positions::RosterPosition,
+    roster::RosterId, schedule::Schedule,
+};
+
+/// What the manager could have known about a bench player at lineup lock.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionContext {
+    /// Healthy and playing; benching them was a bad decision
+    Available,
+    /// Listed with an injury designation that made them unlikely to play
+    Injured(String),
+    /// Their team had no game that week
+    Bye,
+}
+
+impl DecisionContext {
+    /// Whether the outcome couldn't reasonably have been foreseen.
+    pub fn is_unknowable(&self) -> bool {
+        !matches!(self, DecisionContext::Available)
+    }
+
+    /// A short description of the context, e.g. the injury status.
+    pub fn describe(&self) -> String {
+        match self {
+            DecisionContext::Available => "Available".to_string(),
+            DecisionContext::Injured(status) => status.clone(),
+            DecisionContext::Bye => "Bye".to_string(),
+        }
+    }
+}
+
+/// A bench player who belonged in the optimal lineup.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MissedStart {
+    pub player_id: String,
+    pub points: f32,
+    pub context: DecisionContext,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchDecisionReport {
+    pub week: i32,
+    pub roster_id: RosterId,
+    pub missed_starts: Vec<MissedStart>,
+}
+
+impl BenchDecisionReport {
+    /// Points from missed starts the manager should have made.
+    pub fn bad_decision_points(&self) -> f32 {
+        self.missed_starts
+            .iter()
+            .filter(|m| !m.context.is_unknowable())
+            .map(|m| m.points)
+            .sum()
+    }
+
+    /// Points from missed starts that were out, doubtful or on bye at lock.
+    pub fn unknowable_points(&self) -> f32 {
+        self.missed_starts
+            .iter()
+            .filter(|m| m.context.is_unknowable())
+            .map(|m| m.points)
+            .sum()
+    }
+
+    pub fn describe(&self, labels: &OwnerLabels, owner_id: &str, players: &Players) -> String {
+        let flagged = self
+            .missed_starts
+            .iter()
+            .filter(|m| m.context.is_unknowable())
+            .map(|m| {
+                let name = players
+                    .get(&m.player_id)
+                    .and_then(|p| p.full_name.clone())
+                    .unwrap_or(m.player_id.clone());
+                format!(
+                    "{} ({}, {})",
+                    name,
+                    m.context.describe(),
+                    round_to_two_decimals(m.points)
+                )
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "Owner {} benched {} points on bad decisions and {} points that were unknowable at lock{}",
+            labels.label(self.roster_id, owner_id),
+            round_to_two_decimals(self.bad_decision_points()),
+            round_to_two_decimals(self.unknowable_points()),
+            if flagged.is_empty() {
+                "".to_string()
+            } else {
+                format!(": {}", flagged.join(", "))
+            }
+        )
+    }
+}
+
+/// Classifies each bench player in the optimal lineup by what was known at
+/// lock. Injury statuses come from the week's `injuries` snapshot and byes from
+/// the `schedule`, when one is available.
+pub fn calculate_bench_decisions_for_week(
+    week: i32,
+    matchups: &[Matchup],
+    players: &Players,
+    league_positions: &[RosterPosition],
+    injuries: &InjurySnapshot,
+    schedule: Option<&Schedule>,
+) -> Vec<BenchDecisionReport> {
+    matchups
+        .iter()
+        .map(|matchup| {
+            let candidates = matchup
+                .players
+                .iter()
+                .filter_map(|p| players.get(p))
+                .collect::<Vec<_>>();
+            let lineup = optimal_lineup(league_positions, &candidates, &matchup.players_points);
+            let missed_starts = lineup
+                .into_iter()
+                .map(|(_, player_id)| player_id)
+                .filter(|player_id| !matchup.starters.contains(player_id))
+                .map(|player_id| {
+                    let on_bye = schedule
+                        .zip(players.get(&player_id).and_then(|p| p.team.as_ref()))
+                        .map(|(schedule, team)| schedule.is_bye(week, team))
+                        .unwrap_or(false);
+                    let context = if on_bye {
+                        DecisionContext::Bye
+                    } else if injuries.is_unlikely_to_play(&player_id) {
+                        DecisionContext::Injured(injuries.status(&player_id).unwrap().clone())
+                    } else {
+                        DecisionContext::Available
+                    };
+                    MissedStart {
+                        points: *matchup.players_points.get(&player_id).unwrap_or(&0.0f32),
+                        player_id,
+                        context,
+                    }
+                })
+                .collect();
+            BenchDecisionReport {
+                week,
+                roster_id: matchup.roster_id,
+                missed_starts,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn separates_injured_bench_players() {
+        let players: Players = serde_json::from_str(
+            r#"{
+                "1": {"active": true, "player_id": "1", "sport": "nfl", "fantasy_positions": ["RB"]},
+                "2": {"active": true, "player_id": "2", "sport": "nfl", "fantasy_positions": ["RB"]},
+                "3": {"active": true, "player_id": "3", "sport": "nfl", "fantasy_positions": ["RB"]}
+            }"#,
+        )
+        .unwrap();
+        let matchup = Matchup {
+            starters: vec!["1".to_string(), "2".to_string()],
+            roster_id: 1,
+            players: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+            matchup_id: 1,
+            points: 5.0,
+            custom_points: None,
+            players_points: HashMap::from([
+                ("1".to_string(), 2.0),
+                ("2".to_string(), 3.0),
+                ("3".to_string(), 25.0),
+            ]),
+            starters_points: vec![2.0, 3.0],
+        };
+        let mut injuries = InjurySnapshot::default();
+        let decisions = calculate_bench_decisions_for_week(
+            1,
+            std::slice::from_ref(&matchup),
+            &players,
+            &[RosterPosition::RB, RosterPosition::RB],
+            &injuries,
+            None,
+        );
+        assert_eq!(decisions[0].bad_decision_points(), 25.0);
+
+        injuries
+            .statuses
+            .insert("3".to_string(), "Doubtful".to_string());
+        let decisions = calculate_bench_decisions_for_week(
+            1,
+            &[matchup],
+            &players,
+            &[RosterPosition::RB, RosterPosition::RB],
+            &injuries,
+            None,
+        );
+        assert_eq!(decisions[0].bad_decision_points(), 0.0);
+        assert_eq!(decisions[0].unknowable_points(), 25.0);
+        assert_eq!(
+            decisions[0].missed_starts[0].context,
+            DecisionContext::Injured("Doubtful".to_string())
+        );
+    }
+}
@@ -1,20 +1,45 @@
-use super::{calculate_optimal_points::optimal_score_for_matchup, report::Report};
+use super::{
+    calculate_achievable_points::achievable_score_for_matchup,
+    calculate_optimal_points::optimal_score_for_matchup, report::Report,
+};
 use crate::models::*;
 
+/// Reports are attributed to each roster's `owner_id`, so pass the rosters as
+/// they were for `week` when ownership has changed since.
+///
+/// With a `schedule` covering the week, reports also carry the achievable
+/// optimum, with decisions locked from the week's main slate kickoff.
+///
+/// Fails when a matchup's roster isn't in `rosters`.
 pub fn calculate_bench_king_for_week(
+    week: i32,
     matchups: Vec<matchup::Matchup>,
-    rosters: &Vec<roster::Roster>,
+    rosters: &[roster::Roster],
     players: &player::Players,
     league: &league::League,
-    owners: &Vec<user::LeagueUser>,
-) -> Vec<Report> {
+    schedule: Option<&schedule::Schedule>,
+) -> Result<Vec<Report>, String> {
+    let cutoff = schedule.and_then(|s| Some((s, s.main_slate_kickoff(week)?)));
     let mut optimals = vec![];
     for matchup in matchups {
         let roster = rosters
             .iter()
             .find(|r| r.roster_id == matchup.roster_id)
-            .unwrap();
-        //println!("======== Owner: {:?} ========", owners.clone().into_iter().filter(|f| f.user_id == roster.owner_id).map(|m| m.display_name).collect::<Vec<_>>());
+            .ok_or(format!(
+                "No roster {} for week {} matchup {}",
+                matchup.roster_id, week, matchup.matchup_id
+            ))?;
+        let achievable_points = cutoff.map(|(schedule, cutoff)| {
+            achievable_score_for_matchup(
+                &matchup,
+                players,
+                &league.roster_positions,
+                schedule,
+                week,
+                cutoff,
+            )
+            .achievable_points
+        });
         let optimal_roster = optimal_score_for_matchup(
             matchup.clone(),
             roster.clone(),
@@ -23,16 +48,85 @@ pub fn calculate_bench_king_for_week(
         );
 
         let rep = Report {
-            owner_name: owners
-                .iter()
-                .find(|o| o.user_id == optimal_roster.owner_id)
-                .unwrap()
-                .display_name
-                .clone(),
+            week,
+            roster_id: optimal_roster.roster_id,
+            owner_id: optimal_roster.owner_id,
             optimal_points: optimal_roster.optimal_points,
             actual_points: optimal_roster.actual_points,
+            achievable_points,
         };
         optimals.push(rep);
     }
-    optimals
+    Ok(optimals)
+}
+
+/// Bench king for the roster `owner_id` owns or co-owns in the league, if any.
+pub fn calculate_bench_king_for_owner(
+    week: i32,
+    owner_id: &str,
+    matchups: Vec<matchup::Matchup>,
+    rosters: &[roster::Roster],
+    players: &player::Players,
+    league: &league::League,
+    schedule: Option<&schedule::Schedule>,
+) -> Result<Option<Report>, String> {
+    let Some(roster) = rosters
+        .iter()
+        .find(|r| r.owner_id == owner_id || r.co_owners.iter().flatten().any(|c| c == owner_id))
+    else {
+        return Ok(None);
+    };
+    let matchups = matchups
+        .into_iter()
+        .filter(|m| m.roster_id == roster.roster_id)
+        .collect();
+    Ok(calculate_bench_king_for_week(week, matchups, rosters, players, league, schedule)?.pop())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn finds_the_owners_roster() {
+        let mut rosters: Vec<roster::Roster> =
+            serde_json::from_str(include_str!("../../fixtures/rosters.json")).unwrap();
+        rosters[1].co_owners = Some(vec!["200000000000000003".to_string()]);
+        let players: player::Players =
+            serde_json::from_str(include_str!("../../fixtures/players.json")).unwrap();
+        let league: league::League =
+            serde_json::from_str(include_str!("../../fixtures/league.json")).unwrap();
+        let matchups: Vec<matchup::Matchup> =
+            serde_json::from_str(include_str!("../../fixtures/matchups_week_1.json")).unwrap();
+        let for_owner = |owner_id| {
+            calculate_bench_king_for_owner(
+                1,
+                owner_id,
+                matchups.clone(),
+                &rosters,
+                &players,
+                &league,
+                None,
+            )
+        };
+
+        let report = for_owner("200000000000000001").unwrap().unwrap();
+        assert_eq!(report.roster_id, 1);
+        assert_eq!(report.actual_points, 59.3);
+        // Josh Jacobs at RB and Christian McCaffrey at FLEX over Davante Adams
+        assert!((report.optimal_points - 68.0).abs() < 1e-4);
+
+        // Co-owners get the roster's report, attributed to its owner
+        let report = for_owner("200000000000000003").unwrap().unwrap();
+        assert_eq!(report.roster_id, 2);
+        assert_eq!(report.owner_id, "200000000000000002");
+
+        assert!(for_owner("200000000000000004").unwrap().is_none());
+
+        let mut matchups = matchups.clone();
+        matchups[1].roster_id = 3;
+        assert!(
+            calculate_bench_king_for_week(1, matchups, &rosters, &players, &league, None).is_err()
+        );
+    }
 }
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::report::{round_to_two_decimals, OwnerLabels};
+use crate::models::{
+    matchup::Matchup,
+    player::Players,
+    positions::RosterPosition,
+    roster::{Roster, RosterId},
+    user::UserId,
+};
+
+/// How one position group on a best-ball roster contributed over the season.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PositionContribution {
+    pub position: RosterPosition,
+    /// Distinct players at the position that were rostered at some point
+    pub players_rostered: u16,
+    /// Points that counted towards the team score
+    pub counted_points: f32,
+    /// Points scored by every rostered player at the position, counted or not
+    pub rostered_points: f32,
+}
+
+impl PositionContribution {
+    /// Share of the position's rostered points that made it into the lineup.
+    pub fn efficiency(&self) -> f32 {
+        if self.rostered_points <= 0.0 {
+            return 1.0;
+        }
+        self.counted_points / self.rostered_points
+    }
+}
+
+/// Bench king is meaningless in best-ball leagues, where Sleeper starts the
+/// optimal lineup automatically. This reports where the score came from instead.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct BestBallReport {
+    pub roster_id: RosterId,
+    pub owner_id: UserId,
+    /// Points that counted towards the team score
+    pub points: f32,
+    /// Counted points from depth players, those counted in fewer than half of
+    /// the weeks they were rostered
+    pub depth_points: f32,
+    /// Points scored by every rostered player, counted or not
+    pub rostered_points: f32,
+    /// Contribution per position, largest counted points first
+    pub positions: Vec<PositionContribution>,
+}
+
+impl BestBallReport {
+    pub fn depth_share(&self) -> f32 {
+        if self.points <= 0.0 {
+            return 0.0;
+        }
+        self.depth_points / self.points
+    }
+
+    /// Roster construction efficiency, the share of all rostered points that counted.
+    pub fn construction_efficiency(&self) -> f32 {
+        if self.rostered_points <= 0.0 {
+            return 1.0;
+        }
+        self.points / self.rostered_points
+    }
+
+    pub fn describe(&self, labels: &OwnerLabels) -> String {
+        let positions = self
+            .positions
+            .iter()
+            .map(|p| {
+                format!(
+                    "{:?} {} ({}% used)",
+                    p.position,
+                    round_to_two_decimals(p.counted_points),
+                    round_to_two_decimals(p.efficiency() * 100.0)
+                )
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "Owner {} scored {} points, {}% from depth, construction efficiency is {}%, positions: {}",
+            labels.label(self.roster_id, &self.owner_id),
+            round_to_two_decimals(self.points),
+            round_to_two_decimals(self.depth_share() * 100.0),
+            round_to_two_decimals(self.construction_efficiency() * 100.0),
+            positions.join(", ")
+        )
+    }
+}
+
+impl std::fmt::Display for BestBallReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.describe(&OwnerLabels::default()))
+    }
+}
+
+/// Builds a best-ball report per roster from every week's matchups. The
+/// counted lineup is taken from `Matchup::starters`, which Sleeper fills with
+/// the auto-started lineup in best-ball leagues.
+pub fn calculate_best_ball_reports(
+    weeks: &[Vec<Matchup>],
+    rosters: &[Roster],
+    players: &Players,
+) -> Vec<BestBallReport> {
+    let mut by_roster: HashMap<RosterId, Vec<&Matchup>> = HashMap::new();
+    for matchup in weeks.iter().flatten() {
+        by_roster
+            .entry(matchup.roster_id)
+            .or_default()
+            .push(matchup);
+    }
+    let mut reports = by_roster
+        .into_iter()
+        .map(|(roster_id, matchups)| {
+            let owner_id = rosters
+                .iter()
+                .find(|r| r.roster_id == roster_id)
+                .map(|r| r.owner_id.clone())
+                .unwrap_or_default();
+            best_ball_report_for_roster(roster_id, owner_id, &matchups, players)
+        })
+        .collect::<Vec<_>>();
+    reports.sort_by_key(|r| r.roster_id);
+    reports
+}
+
+fn best_ball_report_for_roster(
+    roster_id: RosterId,
+    owner_id: UserId,
+    matchups: &[&Matchup],
+    players: &Players,
+) -> BestBallReport {
+    // (weeks rostered, weeks counted) per player
+    let mut appearances: HashMap<&String, (u16, u16)> = HashMap::new();
+    for matchup in matchups {
+        for player_id in &matchup.players {
+            let entry = appearances.entry(player_id).or_default();
+            entry.0 += 1;
+            if matchup.starters.contains(player_id) {
+                entry.1 += 1;
+            }
+        }
+    }
+    let is_depth = |player_id: &String| {
+        appearances
+            .get(player_id)
+            .map(|(rostered, counted)| counted * 2 < *rostered)
+            .unwrap_or(false)
+    };
+
+    let mut report = BestBallReport {
+        roster_id,
+        owner_id,
+        ..Default::default()
+    };
+    let mut positions: HashMap<RosterPosition, PositionContribution> = HashMap::new();
+    for matchup in matchups {
+        for (player_id, points) in &matchup.players_points {
+            let counted = matchup.starters.contains(player_id);
+            report.rostered_points += points;
+            if counted {
+                report.points += points;
+                if is_depth(player_id) {
+                    report.depth_points += points;
+                }
+            }
+            let Some(position) = players.get(player_id).and_then(|p| p.position) else {
+                continue;
+            };
+            let contribution = positions
+                .entry(position)
+                .or_insert_with(|| PositionContribution {
+                    position,
+                    players_rostered: 0,
+                    counted_points: 0.0,
+                    rostered_points: 0.0,
+                });
+            contribution.rostered_points += points;
+            if counted {
+                contribution.counted_points += points;
+            }
+        }
+    }
+    for player_id in appearances.keys() {
+        if let Some(position) = players.get(*player_id).and_then(|p| p.position) {
+            if let Some(contribution) = positions.get_mut(&position) {
+                contribution.players_rostered += 1;
+            }
+        }
+    }
+    report.positions = positions.into_values().collect();
+    report
+        .positions
+        .sort_by(|a, b| b.counted_points.partial_cmp(&a.counted_points).unwrap());
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn players() -> Players {
+        serde_json::from_str(
+            r#"{
+                "1": {"active": true, "player_id": "1", "sport": "nfl", "position": "QB"},
+                "2": {"active": true, "player_id": "2", "sport": "nfl", "position": "WR"},
+                "3": {"active": true, "player_id": "3", "sport": "nfl", "position": "WR"}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn matchup(starters: &[&str], points: &[(&str, f32)]) -> Matchup {
+        Matchup {
+            starters: starters.iter().map(|s| s.to_string()).collect(),
+            roster_id: 1,
+            players: points.iter().map(|(p, _)| p.to_string()).collect(),
+            matchup_id: 1,
+            points: 0.0,
+            custom_points: None,
+            players_points: points
+                .iter()
+                .map(|(p, pts)| (p.to_string(), *pts))
+                .collect(),
+            starters_points: vec![],
+        }
+    }
+
+    #[test]
+    fn depth_and_position_contribution() {
+        let weeks = vec![
+            vec![matchup(
+                &["1", "2"],
+                &[("1", 20.0), ("2", 10.0), ("3", 5.0)],
+            )],
+            vec![matchup(
+                &["1", "2"],
+                &[("1", 15.0), ("2", 12.0), ("3", 2.0)],
+            )],
+            vec![matchup(
+                &["1", "3"],
+                &[("1", 25.0), ("2", 4.0), ("3", 30.0)],
+            )],
+        ];
+        let reports = calculate_best_ball_reports(&weeks, &[], &players());
+        let report = &reports[0];
+        assert_eq!(report.points, 112.0);
+        assert_eq!(report.depth_points, 30.0);
+        assert_eq!(report.rostered_points, 123.0);
+        assert_eq!(report.positions[0].position, RosterPosition::QB);
+        let wr = &report.positions[1];
+        assert_eq!(wr.players_rostered, 2);
+        assert_eq!(wr.counted_points, 52.0);
+        assert_eq!(wr.rostered_points, 63.0);
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::calculate_optimal_points::{lineup_points, optimal_lineup};
+use super::report::{round_to_two_decimals, OwnerLabels};
+use crate::models::{
+    matchup::Matchup,
+    player::{PlayerDetails, Players},
+    positions::RosterPosition,
+    roster::RosterId,
+};
+
+/// Splits a week's bench regret into what projections said to do and what
+/// happened anyway.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecisionQuality {
+    pub week: i32,
+    pub roster_id: RosterId,
+    /// Points the started lineup scored
+    pub actual_points: f32,
+    /// Points the lineup projections favoured would have scored
+    pub projected_lineup_points: f32,
+    /// Points the best lineup in hindsight would have scored
+    pub optimal_points: f32,
+    /// The lineup projections favoured
+    pub projected_starters: Vec<String>,
+}
+
+impl DecisionQuality {
+    /// Points lost by not starting the projection-optimal lineup. Negative
+    /// when the manager beat the projections.
+    pub fn bad_decision_points(&self) -> f32 {
+        self.projected_lineup_points - self.actual_points
+    }
+
+    /// Points even the projection-optimal lineup would have left on the bench.
+    pub fn bad_luck_points(&self) -> f32 {
+        self.optimal_points - self.projected_lineup_points
+    }
+
+    pub fn describe(&self, labels: &OwnerLabels, owner_id: &str) -> String {
+        format!(
+            "Owner {} scored {}, projections would have scored {} and hindsight {}: {} points on decisions, {} points on luck",
+            labels.label(self.roster_id, owner_id),
+            round_to_two_decimals(self.actual_points),
+            round_to_two_decimals(self.projected_lineup_points),
+            round_to_two_decimals(self.optimal_points),
+            round_to_two_decimals(self.bad_decision_points()),
+            round_to_two_decimals(self.bad_luck_points())
+        )
+    }
+}
+
+/// Compares each roster's started lineup with the lineup `projected_points`
+/// favoured and the hindsight optimum, all scored with the week's actual points.
+pub fn calculate_decision_quality_for_week(
+    week: i32,
+    matchups: &[Matchup],
+    players: &Players,
+    league_positions: &[RosterPosition],
+    projected_points: &HashMap<String, f32>,
+) -> Vec<DecisionQuality> {
+    matchups
+        .iter()
+        .map(|matchup| {
+            let candidates = matchup
+                .players
+                .iter()
+                .filter_map(|p| players.get(p))
+                .collect::<Vec<&PlayerDetails>>();
+            let projected = optimal_lineup(league_positions, &candidates, projected_points);
+            let optimal = optimal_lineup(league_positions, &candidates, &matchup.players_points);
+            DecisionQuality {
+                week,
+                roster_id: matchup.roster_id,
+                actual_points: matchup.points,
+                projected_lineup_points: lineup_points(&projected, &matchup.players_points),
+                optimal_points: lineup_points(&optimal, &matchup.players_points),
+                projected_starters: projected
+                    .into_iter()
+                    .map(|(_, player_id)| player_id)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn splits_decisions_from_luck() {
+        let players: Players = serde_json::from_str(
+            r#"{
+                "1": {"active": true, "player_id": "1", "sport": "nfl", "fantasy_positions": ["RB"]},
+                "2": {"active": true, "player_id": "2", "sport": "nfl", "fantasy_positions": ["RB"]},
+                "3": {"active": true, "player_id": "3", "sport": "nfl", "fantasy_positions": ["RB"]}
+            }"#,
+        )
+        .unwrap();
+        // Started 1, projections favoured 2, and 3 went off the bench
+        let matchup = Matchup {
+            starters: vec!["1".to_string()],
+            roster_id: 1,
+            players: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+            matchup_id: 1,
+            points: 4.0,
+            custom_points: None,
+            players_points: HashMap::from([
+                ("1".to_string(), 4.0),
+                ("2".to_string(), 10.0),
+                ("3".to_string(), 25.0),
+            ]),
+            starters_points: vec![4.0],
+        };
+        let projected_points = HashMap::from([
+            ("1".to_string(), 8.0),
+            ("2".to_string(), 14.5),
+            ("3".to_string(), 6.0),
+        ]);
+
+        let decisions = calculate_decision_quality_for_week(
+            1,
+            &[matchup],
+            &players,
+            &[RosterPosition::RB, RosterPosition::BN],
+            &projected_points,
+        );
+        assert_eq!(decisions[0].projected_starters, vec!["2".to_string()]);
+        assert_eq!(decisions[0].bad_decision_points(), 6.0);
+        assert_eq!(decisions[0].bad_luck_points(), 15.0);
+        assert_eq!(
+            decisions[0].optimal_points - decisions[0].actual_points,
+            decisions[0].bad_decision_points() + decisions[0].bad_luck_points()
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use super::calculate_player_usage::PlayerUsage;
+use crate::models::{draft::DraftPick, positions::RosterPosition, roster::RosterId};
+
+const GRADES: [char; 5] = ['A', 'B', 'C', 'D', 'F'];
+
+/// What one draft pick was worth to the roster that made it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DraftPickValue {
+    pub pick_no: u16,
+    pub round: u16,
+    pub roster_id: RosterId,
+    pub player_id: String,
+    pub position: Option<String>,
+    pub is_keeper: bool,
+    /// [`PlayerUsage::started_points`] for the season
+    pub started_points: f32,
+    /// [`PlayerUsage::optimal_points`] for the season
+    pub optimal_points: f32,
+    /// Optimal points above the best drafted player at the position, by
+    /// optimal points, beyond the league's starting spots for it
+    pub value_over_replacement: f32,
+    /// Rank by optimal points among every pick, 1 being the best
+    pub finish: u16,
+}
+
+impl DraftPickValue {
+    /// How many spots ahead of their draft slot the player finished, negative
+    /// when they finished behind it. Sleeper has no ADP, so the draft slot is
+    /// what picks are judged against.
+    pub fn pick_value(&self) -> i32 {
+        self.pick_no as i32 - self.finish as i32
+    }
+}
+
+/// A roster's draft picks judged by the season so far, in pick order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DraftGrade {
+    pub roster_id: RosterId,
+    /// A to F by total value over replacement, relative to the rest of the league
+    pub grade: char,
+    pub picks: Vec<DraftPickValue>,
+}
+
+impl DraftGrade {
+    pub fn started_points(&self) -> f32 {
+        self.picks.iter().map(|p| p.started_points).sum()
+    }
+
+    pub fn optimal_points(&self) -> f32 {
+        self.picks.iter().map(|p| p.optimal_points).sum()
+    }
+
+    pub fn value_over_replacement(&self) -> f32 {
+        self.picks.iter().map(|p| p.value_over_replacement).sum()
+    }
+
+    /// Value over replacement for each round, in round order.
+    pub fn value_by_round(&self) -> Vec<(u16, f32)> {
+        let mut rounds: BTreeMap<u16, f32> = BTreeMap::new();
+        for pick in &self.picks {
+            *rounds.entry(pick.round).or_default() += pick.value_over_replacement;
+        }
+        rounds.into_iter().collect()
+    }
+
+    pub fn steal(&self) -> Option<&DraftPickValue> {
+        self.picks.iter().max_by_key(|p| p.pick_value())
+    }
+
+    pub fn bust(&self) -> Option<&DraftPickValue> {
+        self.picks.iter().min_by_key(|p| p.pick_value())
+    }
+
+    pub fn keepers(&self) -> impl Iterator<Item = &DraftPickValue> {
+        self.picks.iter().filter(|p| p.is_keeper)
+    }
+}
+
+/// Optimal points of the best drafted player at each position, by optimal
+/// points, beyond the league's starting spots for it, i.e. the (starters + 1)th
+/// best. Flex spots aren't counted, and positions with no spare drafted players
+/// have a replacement level of 0.
+fn replacement_levels(
+    picks: &[DraftPickValue],
+    league_positions: &[RosterPosition],
+    teams: usize,
+) -> HashMap<String, f32> {
+    let mut by_position: HashMap<String, Vec<f32>> = HashMap::new();
+    for pick in picks {
+        if let Some(position) = &pick.position {
+            by_position
+                .entry(position.clone())
+                .or_default()
+                .push(pick.optimal_points);
+        }
+    }
+    by_position
+        .into_iter()
+        .map(|(position, mut points)| {
+            points.sort_by(|a, b| b.partial_cmp(a).unwrap());
+            let starters = match position.parse::<RosterPosition>() {
+                Ok(position) => {
+                    league_positions
+                        .iter()
+                        .filter(|p| !p.is_flex() && p.eligible_positions().contains(&position))
+                        .count()
+                        * teams
+                }
+                Err(_) => 0,
+            };
+            let level = points.get(starters).copied().unwrap_or_default();
+            (position, level)
+        })
+        .collect()
+}
+
+/// Grades every roster's draft using the players' weeks on that roster.
+/// Picks without a roster, like those from mock drafts, are skipped.
+pub fn calculate_draft_grades(
+    picks: &[DraftPick],
+    usage: &PlayerUsage,
+    league_positions: &[RosterPosition],
+) -> Vec<DraftGrade> {
+    let mut values = picks
+        .iter()
+        .filter_map(|pick| {
+            let roster_id = pick.roster_id.as_ref()?.parse().ok()?;
+            Some(DraftPickValue {
+                pick_no: pick.pick_no,
+                round: pick.round,
+                roster_id,
+                player_id: pick.player_id.clone(),
+                position: pick.metadata.as_ref().and_then(|m| m.position.clone()),
+                is_keeper: pick.is_keeper.unwrap_or(false),
+                started_points: usage.started_points(roster_id, &pick.player_id, 1),
+                optimal_points: usage.optimal_points(roster_id, &pick.player_id, 1),
+                value_over_replacement: 0.0,
+                finish: 0,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut order = (0..values.len()).collect::<Vec<_>>();
+    order.sort_by(|a, b| {
+        values[*b]
+            .optimal_points
+            .partial_cmp(&values[*a].optimal_points)
+            .unwrap()
+            .then(values[*a].pick_no.cmp(&values[*b].pick_no))
+    });
+    for (rank, idx) in order.into_iter().enumerate() {
+        values[idx].finish = rank as u16 + 1;
+    }
+
+    let mut grades: BTreeMap<RosterId, Vec<DraftPickValue>> = BTreeMap::new();
+    for value in &values {
+        grades.entry(value.roster_id).or_default();
+    }
+    let levels = replacement_levels(&values, league_positions, grades.len());
+    for mut value in values {
+        let level = value
+            .position
+            .as_ref()
+            .and_then(|p| levels.get(p))
+            .copied()
+            .unwrap_or_default();
+        value.value_over_replacement = value.optimal_points - level;
+        grades.entry(value.roster_id).or_default().push(value);
+    }
+
+    let mut grades = grades
+        .into_iter()
+        .map(|(roster_id, mut picks)| {
+            picks.sort_by_key(|p| p.pick_no);
+            DraftGrade {
+                roster_id,
+                grade: 'C',
+                picks,
+            }
+        })
+        .collect::<Vec<_>>();
+    grades.sort_by(|a, b| {
+        b.value_over_replacement()
+            .partial_cmp(&a.value_over_replacement())
+            .unwrap()
+    });
+    let teams = grades.len();
+    for (idx, grade) in grades.iter_mut().enumerate() {
+        grade.grade = GRADES[idx * GRADES.len() / teams];
+    }
+    grades
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pick(pick_no: u16, roster_id: RosterId, player_id: &str, position: &str) -> DraftPick {
+        serde_json::from_value(serde_json::json!({
+            "player_id": player_id,
+            "picked_by": "",
+            "roster_id": roster_id.to_string(),
+            "round": pick_no.div_ceil(2),
+            "draft_slot": roster_id,
+            "pick_no": pick_no,
+            "metadata": { "position": position },
+            "is_keeper": pick_no == 1,
+            "draft_id": "1"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn grades_drafts() {
+        let picks = [
+            pick(1, 1, "rb1", "RB"),
+            pick(2, 2, "rb2", "RB"),
+            pick(3, 2, "rb3", "RB"),
+            pick(4, 1, "qb1", "QB"),
+        ];
+        let usage = PlayerUsage::from_optimal_weeks(&[
+            (1, 1, "rb1", 20.0, true),
+            (1, 2, "rb2", 50.0, true),
+            (1, 2, "rb3", 10.0, false),
+            (1, 1, "qb1", 25.0, true),
+        ]);
+
+        // One RB spot per team, so the third RB drafted is replacement level
+        let grades = calculate_draft_grades(
+            &picks,
+            &usage,
+            &[RosterPosition::QB, RosterPosition::RB, RosterPosition::BN],
+        );
+        assert_eq!(grades.len(), 2);
+        assert_eq!(grades[0].roster_id, 2);
+        assert_eq!(grades[0].grade, 'A');
+        assert_eq!(grades[0].value_over_replacement(), 40.0);
+        assert_eq!(grades[0].started_points(), 50.0);
+        assert_eq!(grades[0].steal().unwrap().player_id, "rb2");
+        assert_eq!(grades[1].grade, 'C');
+        assert_eq!(grades[1].value_by_round(), vec![(1, 10.0), (2, 25.0)]);
+        assert_eq!(grades[1].bust().unwrap().player_id, "rb1");
+        assert_eq!(grades[1].keepers().count(), 1);
+    }
+}
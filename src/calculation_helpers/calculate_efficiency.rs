@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::report::{round_to_two_decimals, OwnerLabels, Report};
+use crate::models::{roster::RosterId, user::UserId};
+
+/// A roster's weekly reports for a season, with lineup efficiency statistics.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct SeasonSummary {
+    pub roster_id: RosterId,
+    /// Owners of the roster over the season as `(first week, owner id)`, in week order
+    pub owner_history: Vec<(i32, UserId)>,
+    /// Weekly reports, sorted by week
+    pub weeks: Vec<Report>,
+}
+
+impl SeasonSummary {
+    /// The roster's most recent owner.
+    pub fn owner_id(&self) -> UserId {
+        self.owner_history
+            .last()
+            .map(|(_, owner_id)| owner_id.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn actual_points(&self) -> f32 {
+        self.weeks.iter().map(|r| r.actual_points).sum()
+    }
+
+    pub fn optimal_points(&self) -> f32 {
+        self.weeks.iter().map(|r| r.optimal_points).sum()
+    }
+
+    pub fn difference(&self) -> f32 {
+        self.optimal_points() - self.actual_points()
+    }
+
+    /// Season efficiency, total actual points over total optimal points.
+    pub fn efficiency(&self) -> f32 {
+        self.season_report().efficiency()
+    }
+
+    pub fn mean_weekly_efficiency(&self) -> f32 {
+        if self.weeks.is_empty() {
+            return 0.0;
+        }
+        self.weeks.iter().map(|r| r.efficiency()).sum::<f32>() / self.weeks.len() as f32
+    }
+
+    pub fn median_weekly_efficiency(&self) -> f32 {
+        let mut efficiencies = self
+            .weeks
+            .iter()
+            .map(|r| r.efficiency())
+            .collect::<Vec<_>>();
+        efficiencies.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let len = efficiencies.len();
+        match len {
+            0 => 0.0,
+            _ if len % 2 == 0 => (efficiencies[len / 2 - 1] + efficiencies[len / 2]) / 2.0,
+            _ => efficiencies[len / 2],
+        }
+    }
+
+    pub fn best_week(&self) -> Option<&Report> {
+        self.weeks
+            .iter()
+            .max_by(|a, b| a.efficiency().partial_cmp(&b.efficiency()).unwrap())
+    }
+
+    pub fn worst_week(&self) -> Option<&Report> {
+        self.weeks
+            .iter()
+            .min_by(|a, b| a.efficiency().partial_cmp(&b.efficiency()).unwrap())
+    }
+
+    /// Least-squares slope of weekly efficiency against week number; positive
+    /// means the manager's lineup decisions are improving.
+    pub fn efficiency_trend(&self) -> f32 {
+        let n = self.weeks.len() as f32;
+        if self.weeks.len() < 2 {
+            return 0.0;
+        }
+        let mean_week = self.weeks.iter().map(|r| r.week as f32).sum::<f32>() / n;
+        let mean_efficiency = self.mean_weekly_efficiency();
+        let (covariance, variance) = self.weeks.iter().fold((0.0, 0.0), |(cov, var), r| {
+            let dx = r.week as f32 - mean_week;
+            (cov + dx * (r.efficiency() - mean_efficiency), var + dx * dx)
+        });
+        if variance == 0.0 {
+            return 0.0;
+        }
+        covariance / variance
+    }
+
+    /// The season rolled up into a single report.
+    pub fn season_report(&self) -> Report {
+        Report {
+            week: self.weeks.last().map(|r| r.week).unwrap_or_default(),
+            roster_id: self.roster_id,
+            owner_id: self.owner_id(),
+            optimal_points: self.optimal_points(),
+            actual_points: self.actual_points(),
+            achievable_points: self
+                .weeks
+                .iter()
+                .map(|r| r.achievable_points)
+                .sum::<Option<f32>>(),
+        }
+    }
+
+    pub fn describe(&self, labels: &OwnerLabels) -> String {
+        let week_label = |r: Option<&Report>| match r {
+            Some(r) => format!(
+                "week {} ({}%)",
+                r.week,
+                round_to_two_decimals(r.efficiency() * 100.0)
+            ),
+            None => "n/a".to_string(),
+        };
+        let mut owner = labels.label(self.roster_id, &self.owner_id());
+        if self.owner_history.len() > 1 {
+            let previous = self.owner_history[..self.owner_history.len() - 1]
+                .iter()
+                .map(|(week, owner_id)| {
+                    format!(
+                        "{} from week {}",
+                        labels.label(self.roster_id, owner_id),
+                        week
+                    )
+                })
+                .collect::<Vec<_>>();
+            owner = format!("{} (previously {})", owner, previous.join(", "));
+        }
+        format!(
+            "Owner {} efficiency is {}%, mean {}%, median {}%, best {}, worst {}, trend {}% per week",
+            owner,
+            round_to_two_decimals(self.efficiency() * 100.0),
+            round_to_two_decimals(self.mean_weekly_efficiency() * 100.0),
+            round_to_two_decimals(self.median_weekly_efficiency() * 100.0),
+            week_label(self.best_week()),
+            week_label(self.worst_week()),
+            round_to_two_decimals(self.efficiency_trend() * 100.0)
+        )
+    }
+}
+
+impl std::fmt::Display for SeasonSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.describe(&OwnerLabels::default()))
+    }
+}
+
+/// Groups weekly reports into one summary per roster, tracking every owner the
+/// roster had along the way.
+pub fn summarize_season(reports: Vec<Report>) -> Vec<SeasonSummary> {
+    let mut summaries: HashMap<RosterId, SeasonSummary> = HashMap::new();
+    for report in reports {
+        let summary = summaries
+            .entry(report.roster_id)
+            .or_insert_with(|| SeasonSummary {
+                roster_id: report.roster_id,
+                ..Default::default()
+            });
+        summary.weeks.push(report);
+    }
+    summaries
+        .into_values()
+        .map(|mut s| {
+            s.weeks.sort_by_key(|r| r.week);
+            for report in &s.weeks {
+                if s.owner_history.last().map(|(_, o)| o) != Some(&report.owner_id) {
+                    s.owner_history.push((report.week, report.owner_id.clone()));
+                }
+            }
+            s
+        })
+        .collect()
+}
+
+/// Season summaries ranked by efficiency, best first. A manager's rank is
+/// their index in the result plus one.
+pub fn calculate_efficiency_leaderboard(reports: Vec<Report>) -> Vec<SeasonSummary> {
+    let mut summaries = summarize_season(reports);
+    summaries.sort_by(|a, b| {
+        b.efficiency()
+            .partial_cmp(&a.efficiency())
+            .unwrap()
+            .then(a.roster_id.cmp(&b.roster_id))
+    });
+    summaries
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn leaderboard_is_keyed_by_roster() {
+        let leaderboard = calculate_efficiency_leaderboard(vec![
+            Report::from_points(1, 1, 80.0, 100.0),
+            Report::from_points(1, 2, 90.0, 100.0),
+            Report::from_points(2, 1, 100.0, 100.0),
+            Report::from_points(2, 2, 50.0, 100.0),
+        ]);
+        assert_eq!(leaderboard.len(), 2);
+        assert_eq!(leaderboard[0].roster_id, 1);
+        assert_eq!(leaderboard[0].efficiency(), 0.9);
+        assert_eq!(leaderboard[1].efficiency(), 0.7);
+    }
+
+    #[test]
+    fn weekly_statistics() {
+        let summary = &summarize_season(vec![
+            Report::from_points(3, 1, 90.0, 100.0),
+            Report::from_points(1, 1, 50.0, 100.0),
+            Report::from_points(2, 1, 60.0, 100.0),
+        ])[0];
+        assert_eq!(summary.weeks[0].week, 1);
+        assert_eq!(summary.median_weekly_efficiency(), 0.6);
+        assert_eq!(summary.best_week().unwrap().week, 3);
+        assert_eq!(summary.worst_week().unwrap().week, 1);
+        assert!((summary.efficiency_trend() - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn tracks_mid_season_ownership_changes() {
+        let mut reports = vec![
+            Report::from_points(1, 1, 90.0, 100.0),
+            Report::from_points(2, 1, 60.0, 100.0),
+            Report::from_points(3, 1, 50.0, 100.0),
+        ];
+        reports[2].owner_id = "new-owner".to_string();
+        let summaries = summarize_season(reports);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(
+            summaries[0].owner_history,
+            vec![(1, "owner-1".to_string()), (3, "new-owner".to_string())]
+        );
+        assert_eq!(summaries[0].owner_id(), "new-owner");
+    }
+}
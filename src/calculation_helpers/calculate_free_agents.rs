@@ -0,0 +1,110 @@
+use super::calculate_optimal_points::can_fill;
+use super::calculate_rosters_at_week::RosterAtWeek;
+use crate::models::{
+    player::{PlayerDetails, Players},
+    positions::RosterPosition,
+    roster::Roster,
+};
+
+/// The league's unrostered player pool: active players for `sport` that no
+/// roster holds and at least one of the league's starting slots can use,
+/// ordered by Sleeper's search rank.
+pub fn calculate_free_agents<'a>(
+    players: &'a Players,
+    rosters: &[Roster],
+    league_positions: &[RosterPosition],
+    sport: &str,
+) -> Vec<&'a PlayerDetails> {
+    free_agent_pool(players, league_positions, sport, |player_id| {
+        rosters.iter().any(|r| r.has_player(player_id))
+    })
+}
+
+/// The unrostered player pool as of a past week, from rebuilt rosters. Players
+/// on a reserve or taxi squad aren't always in a week's matchups, so they can
+/// show up as unrostered.
+pub fn calculate_free_agents_at_week<'a>(
+    players: &'a Players,
+    rosters: &[RosterAtWeek],
+    league_positions: &[RosterPosition],
+    sport: &str,
+) -> Vec<&'a PlayerDetails> {
+    free_agent_pool(players, league_positions, sport, |player_id| {
+        rosters
+            .iter()
+            .any(|r| r.players.iter().any(|p| p == player_id))
+    })
+}
+
+fn free_agent_pool<'a>(
+    players: &'a Players,
+    league_positions: &[RosterPosition],
+    sport: &str,
+    is_rostered: impl Fn(&str) -> bool,
+) -> Vec<&'a PlayerDetails> {
+    let starting_slots = league_positions
+        .iter()
+        .filter(|p| **p != RosterPosition::BN)
+        .collect::<Vec<_>>();
+    let mut free_agents = players
+        .values()
+        .filter(|p| p.active && p.sport == sport)
+        .filter(|p| !is_rostered(&p.player_id))
+        .filter(|p| starting_slots.iter().any(|slot| can_fill(slot, p)))
+        .collect::<Vec<_>>();
+    free_agents.sort_by_key(|p| (p.search_rank.unwrap_or(u32::MAX), p.player_id.clone()));
+    free_agents
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn player(player_id: &str, position: &str, active: bool, search_rank: u32) -> PlayerDetails {
+        serde_json::from_value(serde_json::json!({
+            "player_id": player_id,
+            "active": active,
+            "sport": "nfl",
+            "position": position,
+            "fantasy_positions": [position],
+            "search_rank": search_rank
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn finds_unrostered_players() {
+        let mut rosters: Vec<Roster> =
+            serde_json::from_str(include_str!("../../fixtures/rosters.json")).unwrap();
+        rosters[1].taxi = Some(vec!["7004".to_string()]);
+        let players = [
+            // On roster 1
+            player("4046", "QB", true, 1),
+            player("7000", "WR", true, 30),
+            player("7001", "RB", true, 20),
+            // No kicker slot in the league
+            player("7002", "K", true, 2),
+            player("7003", "RB", false, 3),
+            player("7004", "TE", true, 4),
+        ]
+        .into_iter()
+        .map(|p| (p.player_id.clone(), p))
+        .collect::<Players>();
+
+        let league_positions = [
+            RosterPosition::QB,
+            RosterPosition::RB,
+            RosterPosition::WR,
+            RosterPosition::FLEX,
+            RosterPosition::BN,
+        ];
+        let free_agents = calculate_free_agents(&players, &rosters, &league_positions, "nfl");
+        assert_eq!(
+            free_agents
+                .iter()
+                .map(|p| p.player_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["7001", "7000"]
+        );
+    }
+}
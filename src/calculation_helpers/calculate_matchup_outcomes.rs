@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::calculate_optimal_points::{optimal_score_for_matchup, OptimalScoreForMatchup};
+use crate::models::{matchup::Matchup, player::Players, positions::RosterPosition, roster::*};
+
+/// The head-to-head result of one roster's matchup for a week, alongside what
+/// the result would have been had either side started its optimal lineup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchupOutcome {
+    pub matchup_id: u16,
+    pub roster_id: RosterId,
+    pub opponent_roster_id: RosterId,
+    pub actual_points: f32,
+    pub optimal_points: f32,
+    pub opponent_actual_points: f32,
+    pub opponent_optimal_points: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchupResult {
+    Win,
+    Loss,
+    Tie,
+}
+
+impl MatchupResult {
+    fn from_points(points: f32, opponent_points: f32) -> MatchupResult {
+        if points > opponent_points {
+            MatchupResult::Win
+        } else if points < opponent_points {
+            MatchupResult::Loss
+        } else {
+            MatchupResult::Tie
+        }
+    }
+}
+
+impl MatchupOutcome {
+    /// What actually happened.
+    pub fn result(&self) -> MatchupResult {
+        MatchupResult::from_points(self.actual_points, self.opponent_actual_points)
+    }
+
+    /// The result had this roster started its optimal lineup against the opponent's actual lineup.
+    pub fn result_with_optimal(&self) -> MatchupResult {
+        MatchupResult::from_points(self.optimal_points, self.opponent_actual_points)
+    }
+
+    /// The result had the opponent started its optimal lineup against this roster's actual lineup.
+    pub fn result_against_opponent_optimal(&self) -> MatchupResult {
+        MatchupResult::from_points(self.actual_points, self.opponent_optimal_points)
+    }
+
+    /// The result had both sides started their optimal lineups.
+    pub fn result_both_optimal(&self) -> MatchupResult {
+        MatchupResult::from_points(self.optimal_points, self.opponent_optimal_points)
+    }
+
+    /// A loss that the optimal lineup would have turned into a win.
+    pub fn lost_to_bench(&self) -> bool {
+        self.result() != MatchupResult::Win && self.result_with_optimal() == MatchupResult::Win
+    }
+
+    /// A win that the opponent's optimal lineup would have taken away.
+    pub fn won_thanks_to_opponent_bench(&self) -> bool {
+        self.result() == MatchupResult::Win
+            && self.result_against_opponent_optimal() != MatchupResult::Win
+    }
+}
+
+/// Pairs up optimal scores sharing a `matchup_id`. Rosters without an opponent
+/// for the week (e.g. a bye or an odd number of teams) are left out.
+pub fn pair_matchup_outcomes(scores: &[OptimalScoreForMatchup]) -> Vec<MatchupOutcome> {
+    let mut by_matchup: HashMap<u16, Vec<&OptimalScoreForMatchup>> = HashMap::new();
+    for score in scores {
+        by_matchup.entry(score.matchup_id).or_default().push(score);
+    }
+    let mut outcomes = vec![];
+    for (matchup_id, sides) in by_matchup {
+        if sides.len() != 2 {
+            continue;
+        }
+        for (side, opponent) in [(sides[0], sides[1]), (sides[1], sides[0])] {
+            outcomes.push(MatchupOutcome {
+                matchup_id,
+                roster_id: side.roster_id,
+                opponent_roster_id: opponent.roster_id,
+                actual_points: side.actual_points,
+                optimal_points: side.optimal_points,
+                opponent_actual_points: opponent.actual_points,
+                opponent_optimal_points: opponent.optimal_points,
+            });
+        }
+    }
+    outcomes.sort_by_key(|o| (o.matchup_id, o.roster_id));
+    outcomes
+}
+
+/// Fails when a matchup's roster isn't in `rosters`.
+pub fn calculate_matchup_outcomes_for_week(
+    week: i32,
+    matchups: Vec<Matchup>,
+    rosters: &[Roster],
+    players: &Players,
+    league_positions: &[RosterPosition],
+) -> Result<Vec<MatchupOutcome>, String> {
+    let scores = matchups
+        .into_iter()
+        .map(|matchup| {
+            let roster = rosters
+                .iter()
+                .find(|r| r.roster_id == matchup.roster_id)
+                .ok_or(format!(
+                    "No roster {} for week {} matchup {}",
+                    matchup.roster_id, week, matchup.matchup_id
+                ))?;
+            Ok(optimal_score_for_matchup(
+                matchup,
+                roster.clone(),
+                players.clone(),
+                league_positions.to_vec(),
+            ))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(pair_matchup_outcomes(&scores))
+}
+
+/// Season record for a roster, both as played and as if every manager had
+/// started their optimal lineup every week.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct AlternateStanding {
+    pub roster_id: RosterId,
+    pub wins: u16,
+    pub losses: u16,
+    pub ties: u16,
+    pub optimal_wins: u16,
+    pub optimal_losses: u16,
+    pub optimal_ties: u16,
+    pub games_lost_to_bench: u16,
+    pub games_won_thanks_to_opponent_bench: u16,
+}
+
+impl AlternateStanding {
+    fn record(result: MatchupResult, wins: &mut u16, losses: &mut u16, ties: &mut u16) {
+        match result {
+            MatchupResult::Win => *wins += 1,
+            MatchupResult::Loss => *losses += 1,
+            MatchupResult::Tie => *ties += 1,
+        }
+    }
+}
+
+impl std::fmt::Display for AlternateStanding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Roster {} went {}-{}-{}, optimal record {}-{}-{}, lost {} games to bench decisions",
+            self.roster_id,
+            self.wins,
+            self.losses,
+            self.ties,
+            self.optimal_wins,
+            self.optimal_losses,
+            self.optimal_ties,
+            self.games_lost_to_bench
+        )
+    }
+}
+
+/// Rolls up weekly outcomes into an alternate standings table, sorted by
+/// optimal wins (then actual wins).
+pub fn calculate_alternate_standings(weeks: &[Vec<MatchupOutcome>]) -> Vec<AlternateStanding> {
+    let mut standings: HashMap<RosterId, AlternateStanding> = HashMap::new();
+    for outcome in weeks.iter().flatten() {
+        let standing = standings
+            .entry(outcome.roster_id)
+            .or_insert_with(|| AlternateStanding {
+                roster_id: outcome.roster_id,
+                ..Default::default()
+            });
+        AlternateStanding::record(
+            outcome.result(),
+            &mut standing.wins,
+            &mut standing.losses,
+            &mut standing.ties,
+        );
+        AlternateStanding::record(
+            outcome.result_both_optimal(),
+            &mut standing.optimal_wins,
+            &mut standing.optimal_losses,
+            &mut standing.optimal_ties,
+        );
+        if outcome.lost_to_bench() {
+            standing.games_lost_to_bench += 1;
+        }
+        if outcome.won_thanks_to_opponent_bench() {
+            standing.games_won_thanks_to_opponent_bench += 1;
+        }
+    }
+    let mut standings = standings.into_values().collect::<Vec<_>>();
+    standings.sort_by_key(|s| {
+        (
+            std::cmp::Reverse(s.optimal_wins),
+            std::cmp::Reverse(s.wins),
+            s.roster_id,
+        )
+    });
+    standings
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn score(
+        matchup_id: u16,
+        roster_id: RosterId,
+        actual: f32,
+        optimal: f32,
+    ) -> OptimalScoreForMatchup {
+        OptimalScoreForMatchup {
+            matchup_id,
+            roster_id,
+            owner_id: roster_id.to_string(),
+            actual_points: actual,
+            optimal_points: optimal,
+            optimal_starters: vec![],
+        }
+    }
+
+    #[test]
+    fn pairs_by_matchup_id() {
+        let outcomes = pair_matchup_outcomes(&[
+            score(1, 1, 100.0, 120.0),
+            score(2, 3, 90.0, 95.0),
+            score(1, 2, 110.0, 112.0),
+            score(3, 4, 80.0, 80.0),
+        ]);
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].roster_id, 1);
+        assert_eq!(outcomes[0].opponent_roster_id, 2);
+        assert_eq!(outcomes[1].opponent_roster_id, 1);
+        assert_eq!(outcomes[0].result(), MatchupResult::Loss);
+        assert!(outcomes[0].lost_to_bench());
+        assert!(outcomes[1].won_thanks_to_opponent_bench());
+        assert_eq!(outcomes[0].result_both_optimal(), MatchupResult::Win);
+    }
+
+    #[test]
+    fn alternate_standings() {
+        let week_one =
+            pair_matchup_outcomes(&[score(1, 1, 100.0, 120.0), score(1, 2, 110.0, 112.0)]);
+        let week_two =
+            pair_matchup_outcomes(&[score(1, 1, 100.0, 100.0), score(1, 2, 90.0, 130.0)]);
+        let standings = calculate_alternate_standings(&[week_one, week_two]);
+        assert_eq!(standings[0].roster_id, 1);
+        assert_eq!((standings[0].wins, standings[0].losses), (1, 1));
+        assert_eq!(
+            (standings[0].optimal_wins, standings[0].optimal_losses),
+            (1, 1)
+        );
+        assert_eq!(standings[0].games_lost_to_bench, 1);
+        assert_eq!(standings[0].games_won_thanks_to_opponent_bench, 1);
+        assert_eq!(standings[1].games_lost_to_bench, 1);
+    }
+
+    #[test]
+    fn missing_rosters_are_errors() {
+        let matchups: Vec<Matchup> =
+            serde_json::from_str(include_str!("../../fixtures/matchups_week_1.json")).unwrap();
+        let mut rosters: Vec<Roster> =
+            serde_json::from_str(include_str!("../../fixtures/rosters.json")).unwrap();
+        let outcomes = calculate_matchup_outcomes_for_week(
+            1,
+            matchups.clone(),
+            &rosters,
+            &Players::new(),
+            &[RosterPosition::QB],
+        )
+        .unwrap();
+        assert_eq!(outcomes.len(), 2);
+
+        rosters.pop();
+        assert_eq!(
+            calculate_matchup_outcomes_for_week(
+                1,
+                matchups,
+                &rosters,
+                &Players::new(),
+                &[RosterPosition::QB]
+            ),
+            Err("No roster 2 for week 1 matchup 1".to_string())
+        );
+    }
+}
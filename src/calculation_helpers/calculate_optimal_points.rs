@@ -7,13 +7,14 @@ use crate::models::{
     roster::{Roster, RosterId},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OptimalScoreForMatchup {
     pub matchup_id: u16,
     pub roster_id: RosterId,
     pub owner_id: String,
     pub actual_points: f32,
     pub optimal_points: f32,
+    pub optimal_starters: Vec<String>,
 }
 
 impl std::fmt::Display for OptimalScoreForMatchup {
@@ -32,15 +33,44 @@ pub fn optimal_score_for_matchup(
     players: Players,
     league_positions: Vec<RosterPosition>,
 ) -> OptimalScoreForMatchup {
-    //println!("Matchup Points: {:?}", matchup.points);
-    let mut optimal_roster = OptimalScoreForMatchup {
+    let viable_players_with_stats = players
+        .iter()
+        .filter(|(player_id, _)| matchup.players.iter().any(|p| &p == player_id))
+        .map(|(_, details)| details)
+        .collect::<Vec<&PlayerDetails>>();
+
+    let lineup = optimal_lineup(
+        &league_positions,
+        &viable_players_with_stats,
+        &matchup.players_points,
+    );
+    OptimalScoreForMatchup {
         matchup_id: matchup.matchup_id,
         roster_id: roster.roster_id,
         owner_id: roster.owner_id,
         actual_points: matchup.points,
-        optimal_points: 0.0,
-    };
-    let mut roster_position_count = league_positions
+        optimal_points: lineup_points(&lineup, &matchup.players_points),
+        optimal_starters: lineup.into_iter().map(|(_, player_id)| player_id).collect(),
+    }
+}
+
+/// Whether the player's fantasy positions let them start in `slot`.
+pub fn can_fill(slot: &RosterPosition, player: &PlayerDetails) -> bool {
+    match &player.fantasy_positions {
+        Some(fp) => fp.iter().any(|p| slot.can_hold(p)),
+        None => false,
+    }
+}
+
+/// Greedily fills the non-bench `slots` from `candidates`, most restrictive
+/// slots first, returning the chosen `(slot, player id)` pairs. Slots without
+/// an eligible player left are skipped.
+pub fn optimal_lineup(
+    slots: &[RosterPosition],
+    candidates: &[&PlayerDetails],
+    players_points: &HashMap<String, f32>,
+) -> Vec<(RosterPosition, String)> {
+    let mut roster_position_count = slots
         .iter()
         .fold(HashMap::new(), |mut acc, pos| {
             if pos == &RosterPosition::BN {
@@ -55,65 +85,99 @@ pub fn optimal_score_for_matchup(
         .collect::<Vec<(RosterPosition, u8)>>();
 
     // Sort by the number of football positions a fantasy position can hold
-    roster_position_count.sort_by_key(|a| (a.0.value().len(), a.1));
-    //println!("Roster Position Count: {:?}", roster_position_count);
+    roster_position_count.sort_by_key(|a| (a.0.eligible_positions().len(), a.1));
 
-    let viable_players_with_stats = players
-        .iter()
-        .filter(|(player_id, _)| matchup.players.iter().any(|p| &p == player_id))
-        .map(|(_, details)| details)
-        .collect::<Vec<&PlayerDetails>>();
-    //println!("Viable Players: {:?}", viable_players_with_stats.iter().map(|p| (p.full_name.as_ref().unwrap_or(&p.player_id), p.position.unwrap_or(RosterPosition::SuperFlex))).collect::<Vec<(&String, RosterPosition)>>());
+    let mut used_players: Vec<String> = Vec::new();
+    let mut lineup = Vec::new();
 
-    let mut used_players = Vec::new();
-    
     for (position, count) in roster_position_count {
-        //println!("Drafting {} players for position {:?}", count, position);
-        let mut players_for_position = viable_players_with_stats
+        let mut players_for_position = candidates
             .iter()
-            .filter(|&player| match &player.fantasy_positions {
-                Some(fp) => {
-                    fp.iter().any(|p| position.value().iter().any(|v| p.value().contains(v)))
-                },
-                None => false,
-            })
-            .map(|p| p.to_owned().to_owned())
-            .collect::<Vec<PlayerDetails>>();
+            .filter(|player| can_fill(&position, player))
+            .filter(|player| !used_players.contains(&player.player_id))
+            .collect::<Vec<_>>();
         players_for_position.sort_by(|a, b| {
-            let a_pts = matchup.players_points.get(&a.player_id).unwrap_or(&0.0f32);
-            let b_pts = matchup.players_points.get(&b.player_id).unwrap_or(&0.0f32);
+            let a_pts = players_points.get(&a.player_id).unwrap_or(&0.0f32);
+            let b_pts = players_points.get(&b.player_id).unwrap_or(&0.0f32);
             a_pts.partial_cmp(b_pts).unwrap()
         });
-        // println!("Players for position: {:?}", players_for_position.iter().map(|p| match &p.full_name {
-        //     Some(name) => (name.to_string(), matchup.players_points.get(&p.player_id).unwrap_or(&0.0f32)),
-        //     None => (p.player_id.to_string(), matchup.players_points.get(&p.player_id).unwrap_or(&0.0f32)),
-        // }).collect::<Vec<(String,&f32)>>());
         for _l in 0..count {
-            //println!("Looop count: {}, Player Count: {}, Position: {:?}", l, players_for_position.len(), position.value());
-            let (player, up, pfp) = get_player(&used_players, players_for_position);
-            used_players = [used_players, up].concat().clone();
-            players_for_position = pfp;
-            let player_id = &player.player_id;
-            let points = matchup.players_points.get(player_id).unwrap_or(&0.0f32);
-            //println!("Player: {:?}; Points: {}", player.full_name.as_ref().unwrap_or(&player.player_id), points);
-            optimal_roster.optimal_points += points;
-            used_players.push(player_id.to_string());
+            let Some(player) = players_for_position.pop() else {
+                break;
+            };
+            used_players.push(player.player_id.clone());
+            lineup.push((position, player.player_id.clone()));
         }
     }
-    optimal_roster
+    lineup
 }
 
-fn get_player(used_players: &Vec<String>, mut players: Vec<PlayerDetails>) -> (PlayerDetails, Vec<String>, Vec<PlayerDetails>) {
-    match players.pop() {
-        Some(p) => {
-            if used_players.contains(&p.player_id) {
-                //println!("Player already used: {:?}", p.full_name.as_ref().unwrap_or(&p.player_id));
-                get_player(&[used_players.clone(), vec![p.player_id.clone()]].concat(), players.clone());
-            }
-            (p.clone().clone(), [used_players.clone(), vec![p.player_id.clone()]].concat(), players)
-        },
-        None => {
-            get_player(used_players, players)
-        },
+pub fn lineup_points(
+    lineup: &[(RosterPosition, String)],
+    players_points: &HashMap<String, f32>,
+) -> f32 {
+    lineup
+        .iter()
+        .map(|(_, player_id)| players_points.get(player_id).unwrap_or(&0.0f32))
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn player(player_id: &str, positions: &[&str]) -> PlayerDetails {
+        serde_json::from_value(serde_json::json!({
+            "player_id": player_id,
+            "active": true,
+            "sport": "nfl",
+            "fantasy_positions": positions
+        }))
+        .unwrap()
+    }
+
+    fn points(points: &[(&str, f32)]) -> HashMap<String, f32> {
+        points
+            .iter()
+            .map(|(player_id, points)| (player_id.to_string(), *points))
+            .collect()
+    }
+
+    #[test]
+    fn starts_each_player_once() {
+        // The RB slot takes rb1, so FLEX has to settle for rb2 rather than
+        // counting rb1's points a second time.
+        let candidates = [player("rb1", &["RB"]), player("rb2", &["RB"])];
+        let candidates = candidates.iter().collect::<Vec<_>>();
+        let players_points = points(&[("rb1", 20.0), ("rb2", 5.0)]);
+
+        let lineup = optimal_lineup(
+            &[RosterPosition::RB, RosterPosition::FLEX, RosterPosition::BN],
+            &candidates,
+            &players_points,
+        );
+        assert_eq!(
+            lineup,
+            vec![
+                (RosterPosition::RB, "rb1".to_string()),
+                (RosterPosition::FLEX, "rb2".to_string()),
+            ]
+        );
+        assert_eq!(lineup_points(&lineup, &players_points), 25.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn leaves_slots_without_eligible_players_empty() {
+        let candidates = [player("qb", &["QB"])];
+        let candidates = candidates.iter().collect::<Vec<_>>();
+        let players_points = points(&[("qb", 18.0)]);
+
+        let lineup = optimal_lineup(
+            &[RosterPosition::QB, RosterPosition::WR, RosterPosition::WR],
+            &candidates,
+            &players_points,
+        );
+        assert_eq!(lineup, vec![(RosterPosition::QB, "qb".to_string())]);
+        assert_eq!(lineup_points(&lineup, &players_points), 18.0);
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use super::calculate_optimal_points::optimal_lineup;
+use crate::models::{
+    matchup::Matchup,
+    player::{PlayerDetails, Players},
+    positions::RosterPosition,
+    roster::RosterId,
+};
+
+/// A player's score for the roster they were on in one week.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerWeek {
+    pub week: i32,
+    pub roster_id: RosterId,
+    pub player_id: String,
+    pub points: f32,
+    pub started: bool,
+    /// Whether the player was in the roster's optimal lineup
+    pub optimal: bool,
+}
+
+/// Every rostered player's weekly score, with whether they were started and
+/// whether they belonged in the optimal lineup. Used to credit players to the
+/// rosters they played for, e.g. after a trade or waiver claim.
+#[derive(Debug, Default, Clone)]
+pub struct PlayerUsage {
+    weeks: HashMap<(RosterId, String), Vec<PlayerWeek>>,
+}
+
+impl PlayerUsage {
+    /// `weeks` holds each week's matchups in order, `weeks[0]` being week 1.
+    pub fn calculate(
+        weeks: &[Vec<Matchup>],
+        players: &Players,
+        league_positions: &[RosterPosition],
+    ) -> PlayerUsage {
+        let mut usage = PlayerUsage::default();
+        for (idx, matchups) in weeks.iter().enumerate() {
+            for matchup in matchups {
+                let candidates = matchup
+                    .players
+                    .iter()
+                    .filter_map(|p| players.get(p))
+                    .collect::<Vec<&PlayerDetails>>();
+                let optimal =
+                    optimal_lineup(league_positions, &candidates, &matchup.players_points)
+                        .into_iter()
+                        .map(|(_, player_id)| player_id)
+                        .collect::<Vec<_>>();
+                for player_id in &matchup.players {
+                    usage.insert(PlayerWeek {
+                        week: idx as i32 + 1,
+                        roster_id: matchup.roster_id,
+                        player_id: player_id.clone(),
+                        points: matchup
+                            .players_points
+                            .get(player_id)
+                            .copied()
+                            .unwrap_or_default(),
+                        started: matchup.starters.contains(player_id),
+                        optimal: optimal.contains(player_id),
+                    });
+                }
+            }
+        }
+        usage
+    }
+
+    pub fn insert(&mut self, week: PlayerWeek) {
+        self.weeks
+            .entry((week.roster_id, week.player_id.clone()))
+            .or_default()
+            .push(week);
+    }
+
+    /// The player's weeks on the roster from `from_week` on.
+    pub fn weeks_for(
+        &self,
+        roster_id: RosterId,
+        player_id: &str,
+        from_week: i32,
+    ) -> impl Iterator<Item = &PlayerWeek> {
+        self.weeks
+            .get(&(roster_id, player_id.to_string()))
+            .into_iter()
+            .flatten()
+            .filter(move |w| w.week >= from_week)
+    }
+
+    /// Points the player scored for the roster in weeks it started them, from
+    /// `from_week` on. What the roster actually got out of the player.
+    pub fn started_points(&self, roster_id: RosterId, player_id: &str, from_week: i32) -> f32 {
+        self.weeks_for(roster_id, player_id, from_week)
+            .filter(|w| w.started)
+            .map(|w| w.points)
+            .sum()
+    }
+
+    /// Points the player scored for the roster in weeks they belonged in its
+    /// optimal lineup, started or not, from `from_week` on. What the roster could
+    /// have got out of the player.
+    pub fn optimal_points(&self, roster_id: RosterId, player_id: &str, from_week: i32) -> f32 {
+        self.weeks_for(roster_id, player_id, from_week)
+            .filter(|w| w.optimal)
+            .map(|w| w.points)
+            .sum()
+    }
+
+    /// Points the player scored in weeks other rosters started them, from `from_week` on.
+    pub fn started_points_elsewhere(
+        &self,
+        roster_id: RosterId,
+        player_id: &str,
+        from_week: i32,
+    ) -> f32 {
+        self.weeks
+            .iter()
+            .filter(|((r, p), _)| *r != roster_id && p == player_id)
+            .flat_map(|(_, weeks)| weeks)
+            .filter(|w| w.week >= from_week && w.started)
+            .map(|w| w.points)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+impl PlayerUsage {
+    /// Usage from `(week, roster_id, player_id, points, started)` weeks that
+    /// were all in the roster's optimal lineup.
+    pub(crate) fn from_optimal_weeks(weeks: &[(i32, RosterId, &str, f32, bool)]) -> PlayerUsage {
+        let mut usage = PlayerUsage::default();
+        for (week, roster_id, player_id, points, started) in weeks {
+            usage.insert(PlayerWeek {
+                week: *week,
+                roster_id: *roster_id,
+                player_id: player_id.to_string(),
+                points: *points,
+                started: *started,
+                optimal: true,
+            });
+        }
+        usage
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::league::League;
+
+    #[test]
+    fn splits_started_and_optimal_points() {
+        let weeks: Vec<Vec<Matchup>> = vec![
+            serde_json::from_str(include_str!("../../fixtures/matchups_week_1.json")).unwrap(),
+            serde_json::from_str(include_str!("../../fixtures/matchups_week_2.json")).unwrap(),
+        ];
+        let players: Players =
+            serde_json::from_str(include_str!("../../fixtures/players.json")).unwrap();
+        let league: League =
+            serde_json::from_str(include_str!("../../fixtures/league.json")).unwrap();
+        let usage = PlayerUsage::calculate(&weeks, &players, &league.roster_positions);
+
+        // Davante Adams started both weeks but never belonged in the lineup
+        assert_eq!(usage.weeks_for(1, "2133", 1).count(), 2);
+        assert_eq!(usage.started_points(1, "2133", 2), 6.1);
+        assert_eq!(usage.optimal_points(1, "2133", 1), 0.0);
+        // Josh Jacobs sat on the bench in week 1 when he belonged at RB
+        assert_eq!(usage.started_points(1, "5850", 1), 0.0);
+        assert_eq!(usage.optimal_points(1, "5850", 1), 18.5);
+        // Patrick Mahomes was started and optimal both weeks
+        assert_eq!(usage.started_points(1, "4046", 1), 40.4);
+        assert_eq!(
+            usage.started_points(1, "4046", 1),
+            usage.optimal_points(1, "4046", 1)
+        );
+        assert_eq!(usage.started_points_elsewhere(2, "4046", 1), 40.4);
+        assert_eq!(usage.started_points_elsewhere(1, "4046", 1), 0.0);
+        assert_eq!(usage.weeks_for(2, "4046", 1).count(), 0);
+    }
+}
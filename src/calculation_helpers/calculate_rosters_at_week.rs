@@ -0,0 +1,138 @@
+use serde::Serialize;
+
+use crate::models::{
+    matchup::Matchup,
+    roster::{Roster, RosterId},
+    transaction::{Transaction, TransactionType},
+    user::UserId,
+};
+
+/// How a player joined a roster, from the transactions log.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Acquisition {
+    pub player_id: String,
+    pub week: i32,
+    pub transaction_type: TransactionType,
+    pub transaction_id: String,
+}
+
+/// Who was on a roster for a past week. `Roster` from the API only reflects
+/// today's roster, so use this when pairing rosters with old weeks.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RosterAtWeek {
+    pub week: i32,
+    pub roster_id: RosterId,
+    /// The current owner; ownership changes aren't in the transactions log
+    pub owner_id: UserId,
+    pub players: Vec<String>,
+    /// Empty when the week was reconstructed from transactions
+    pub starters: Vec<String>,
+    /// Players on the roster that week who joined through a transaction, the
+    /// rest were drafted or kept
+    pub acquisitions: Vec<Acquisition>,
+}
+
+impl RosterAtWeek {
+    pub fn acquisition(&self, player_id: &str) -> Option<&Acquisition> {
+        self.acquisitions.iter().find(|a| a.player_id == player_id)
+    }
+}
+
+/// Complete transactions in the order they were processed.
+fn processed_in_order(transactions: &[Transaction]) -> Vec<&Transaction> {
+    let mut transactions = transactions
+        .iter()
+        .filter(|t| t.is_complete())
+        .collect::<Vec<_>>();
+    transactions.sort_by_key(|t| (t.leg, t.status_updated.unwrap_or(t.created)));
+    transactions
+}
+
+/// Rebuilds every roster as of `week`. Rosters with a matchup that week use
+/// `Matchup::players`, which Sleeper records as the roster was then. Otherwise
+/// transactions after `week` are undone from today's roster. `transactions`
+/// should cover every week after `week` for the reconstruction, and every
+/// week up to it for the acquisitions.
+pub fn calculate_rosters_at_week(
+    week: i32,
+    matchups: &[Matchup],
+    current_rosters: &[Roster],
+    transactions: &[Transaction],
+) -> Vec<RosterAtWeek> {
+    let transactions = processed_in_order(transactions);
+    current_rosters
+        .iter()
+        .map(|roster| {
+            let (players, starters) =
+                match matchups.iter().find(|m| m.roster_id == roster.roster_id) {
+                    Some(matchup) => (matchup.players.clone(), matchup.starters.clone()),
+                    None => {
+                        let mut players = roster.players.clone().unwrap_or_default();
+                        for transaction in transactions.iter().rev().filter(|t| t.leg > week) {
+                            let added = transaction.added_by(roster.roster_id);
+                            players.retain(|p| !added.contains(p));
+                            for dropped in transaction.dropped_by(roster.roster_id) {
+                                if !players.contains(&dropped) {
+                                    players.push(dropped);
+                                }
+                            }
+                        }
+                        players.sort();
+                        (players, vec![])
+                    }
+                };
+            let acquisitions = players
+                .iter()
+                .filter_map(|player_id| {
+                    let transaction = transactions.iter().rev().find(|t| {
+                        t.leg <= week && t.added_by(roster.roster_id).contains(player_id)
+                    })?;
+                    Some(Acquisition {
+                        player_id: player_id.clone(),
+                        week: transaction.leg,
+                        transaction_type: transaction.transaction_type,
+                        transaction_id: transaction.transaction_id.clone(),
+                    })
+                })
+                .collect();
+            RosterAtWeek {
+                week,
+                roster_id: roster.roster_id,
+                owner_id: roster.owner_id.clone(),
+                players,
+                starters,
+                acquisitions,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reconstructs_rosters() {
+        let rosters: Vec<Roster> =
+            serde_json::from_str(include_str!("../../fixtures/rosters.json")).unwrap();
+        let transactions: Vec<Transaction> =
+            serde_json::from_str(include_str!("../../fixtures/transactions.json")).unwrap();
+        let week_two: Vec<Matchup> =
+            serde_json::from_str(include_str!("../../fixtures/matchups_week_2.json")).unwrap();
+
+        // Without matchups, the week 2 waiver claim is undone for week 1
+        let week_one = calculate_rosters_at_week(1, &[], &rosters, &transactions);
+        assert!(week_one[0].players.contains(&"8146".to_string()));
+        assert!(!week_one[0].players.contains(&"9999".to_string()));
+        assert!(week_one[0].acquisitions.is_empty());
+        // The failed claim is ignored
+        assert!(week_one[1].players.contains(&"7564".to_string()));
+
+        let week_two = calculate_rosters_at_week(2, &week_two, &rosters, &transactions);
+        assert_eq!(week_two[0].starters.len(), 4);
+        let acquisition = week_two[0].acquisition("9999").unwrap();
+        assert_eq!(acquisition.transaction_type, TransactionType::Waiver);
+        assert_eq!(acquisition.week, 2);
+        assert!(week_two[1].acquisitions.is_empty());
+    }
+}
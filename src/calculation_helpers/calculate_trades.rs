@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::calculate_player_usage::PlayerUsage;
+use crate::models::{
+    roster::RosterId,
+    transaction::{TradedPick, Transaction, TransactionType},
+};
+
+/// What a received player scored for their new roster since the trade.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReceivedPoints {
+    /// [`PlayerUsage::started_points`] since the trade
+    pub started_points: f32,
+    /// [`PlayerUsage::optimal_points`] since the trade
+    pub optimal_points: f32,
+}
+
+/// What one roster got out of a trade over the rest of the season.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TradeSide {
+    pub roster_id: RosterId,
+    pub received: Vec<String>,
+    pub sent: Vec<String>,
+    pub received_picks: Vec<TradedPick>,
+    /// FAAB received less FAAB sent
+    pub waiver_budget: i64,
+    /// Points of each received player, by player id
+    pub received_points: BTreeMap<String, ReceivedPoints>,
+    /// [`PlayerUsage::started_points`] of the received players since the trade
+    pub started_points: f32,
+    /// [`PlayerUsage::optimal_points`] of the received players since the trade
+    pub optimal_points: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TradeAnalysis {
+    pub transaction_id: String,
+    pub week: i32,
+    pub sides: Vec<TradeSide>,
+}
+
+impl TradeAnalysis {
+    /// The side that started the most points from the players it received,
+    /// or `None` when no side did better than the rest.
+    pub fn winner(&self) -> Option<RosterId> {
+        let best = self
+            .sides
+            .iter()
+            .max_by(|a, b| a.started_points.partial_cmp(&b.started_points).unwrap())?;
+        let tied = self
+            .sides
+            .iter()
+            .filter(|s| s.started_points == best.started_points)
+            .count();
+        (tied == 1).then_some(best.roster_id)
+    }
+}
+
+/// A roster's trades rolled up over the season.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct TradeLedger {
+    pub roster_id: RosterId,
+    pub trades: u16,
+    pub wins: u16,
+    /// Started points from players received
+    pub points_gained: f32,
+    /// Started points the trade partners got from players this roster sent
+    pub points_given_up: f32,
+    pub optimal_points_gained: f32,
+    pub optimal_points_given_up: f32,
+}
+
+impl TradeLedger {
+    pub fn net_points(&self) -> f32 {
+        self.points_gained - self.points_given_up
+    }
+}
+
+/// Evaluates every completed trade from the week it was made on, in the order
+/// the trades happened.
+pub fn calculate_trade_analyses(
+    transactions: &[Transaction],
+    usage: &PlayerUsage,
+) -> Vec<TradeAnalysis> {
+    let mut trades = transactions
+        .iter()
+        .filter(|t| t.transaction_type == TransactionType::Trade && t.is_complete())
+        .collect::<Vec<_>>();
+    trades.sort_by_key(|t| (t.leg, t.created));
+    trades
+        .into_iter()
+        .map(|trade| TradeAnalysis {
+            transaction_id: trade.transaction_id.clone(),
+            week: trade.leg,
+            sides: trade
+                .roster_ids
+                .iter()
+                .map(|roster_id| {
+                    let received = trade.added_by(*roster_id);
+                    let received_points = received
+                        .iter()
+                        .map(|p| {
+                            (
+                                p.clone(),
+                                ReceivedPoints {
+                                    started_points: usage.started_points(*roster_id, p, trade.leg),
+                                    optimal_points: usage.optimal_points(*roster_id, p, trade.leg),
+                                },
+                            )
+                        })
+                        .collect::<BTreeMap<_, _>>();
+                    TradeSide {
+                        roster_id: *roster_id,
+                        started_points: received_points.values().map(|p| p.started_points).sum(),
+                        optimal_points: received_points.values().map(|p| p.optimal_points).sum(),
+                        received,
+                        received_points,
+                        sent: trade.dropped_by(*roster_id),
+                        received_picks: trade
+                            .draft_picks
+                            .iter()
+                            .filter(|p| p.owner_id == *roster_id)
+                            .cloned()
+                            .collect(),
+                        waiver_budget: trade
+                            .waiver_budget
+                            .iter()
+                            .map(|w| {
+                                if w.receiver == *roster_id {
+                                    w.amount as i64
+                                } else if w.sender == *roster_id {
+                                    -(w.amount as i64)
+                                } else {
+                                    0
+                                }
+                            })
+                            .sum(),
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Per-roster trade ledgers, best net points first.
+pub fn calculate_trade_ledgers(analyses: &[TradeAnalysis]) -> Vec<TradeLedger> {
+    let mut ledgers: BTreeMap<RosterId, TradeLedger> = BTreeMap::new();
+    for analysis in analyses {
+        let winner = analysis.winner();
+        for side in &analysis.sides {
+            // Only the players this side sent count, partners in a three-way
+            // trade may also have received players from someone else
+            let (given_up, optimal_given_up) = analysis
+                .sides
+                .iter()
+                .filter(|s| s.roster_id != side.roster_id)
+                .flat_map(|s| side.sent.iter().filter_map(|p| s.received_points.get(p)))
+                .fold((0.0, 0.0), |(started, optimal), p| {
+                    (started + p.started_points, optimal + p.optimal_points)
+                });
+            let ledger = ledgers
+                .entry(side.roster_id)
+                .or_insert_with(|| TradeLedger {
+                    roster_id: side.roster_id,
+                    ..Default::default()
+                });
+            ledger.trades += 1;
+            if winner == Some(side.roster_id) {
+                ledger.wins += 1;
+            }
+            ledger.points_gained += side.started_points;
+            ledger.optimal_points_gained += side.optimal_points;
+            ledger.points_given_up += given_up;
+            ledger.optimal_points_given_up += optimal_given_up;
+        }
+    }
+    let mut ledgers = ledgers.into_values().collect::<Vec<_>>();
+    ledgers.sort_by(|a, b| b.net_points().partial_cmp(&a.net_points()).unwrap());
+    ledgers
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn evaluates_trades() {
+        let trade: Transaction = serde_json::from_str(
+            r#"{
+                "type": "trade",
+                "transaction_id": "1",
+                "status_updated": 1,
+                "status": "complete",
+                "settings": null,
+                "roster_ids": [1, 2],
+                "leg": 3,
+                "drops": {"a": 1, "b": 2},
+                "adds": {"a": 2, "b": 1},
+                "draft_picks": [{ "season": "2025", "round": 2, "roster_id": 1, "previous_owner_id": 1, "owner_id": 2 }],
+                "creator": "1",
+                "created": 1,
+                "consenter_ids": [1, 2],
+                "waiver_budget": [{ "sender": 2, "receiver": 1, "amount": 10 }]
+            }"#,
+        )
+        .unwrap();
+        let usage = PlayerUsage::from_optimal_weeks(&[
+            // Before the trade, not credited to either side
+            (2, 1, "a", 30.0, true),
+            (3, 2, "a", 20.0, true),
+            (4, 2, "a", 15.0, false),
+            (3, 1, "b", 8.0, true),
+            (4, 1, "b", 12.0, true),
+        ]);
+
+        let analyses = calculate_trade_analyses(&[trade], &usage);
+        assert_eq!(analyses.len(), 1);
+        let sides = &analyses[0].sides;
+        assert_eq!(
+            (sides[0].started_points, sides[0].optimal_points),
+            (20.0, 20.0)
+        );
+        assert_eq!(
+            (sides[1].started_points, sides[1].optimal_points),
+            (20.0, 35.0)
+        );
+        assert_eq!(sides[0].waiver_budget, 10);
+        assert_eq!(sides[1].received_picks.len(), 1);
+        assert_eq!(analyses[0].winner(), None);
+
+        let ledgers = calculate_trade_ledgers(&analyses);
+        assert_eq!(ledgers.len(), 2);
+        assert_eq!(ledgers[0].net_points(), 0.0);
+        assert_eq!(ledgers[0].optimal_points_given_up, 35.0);
+    }
+
+    #[test]
+    fn three_team_ledgers_only_count_players_sent() {
+        // 1 sends a to 2, 2 sends b to 3 and 3 sends c to 2
+        let trade: Transaction = serde_json::from_str(
+            r#"{
+                "type": "trade",
+                "transaction_id": "2",
+                "status_updated": 1,
+                "status": "complete",
+                "settings": null,
+                "roster_ids": [1, 2, 3],
+                "leg": 5,
+                "drops": {"a": 1, "b": 2, "c": 3},
+                "adds": {"a": 2, "b": 3, "c": 2},
+                "draft_picks": [],
+                "creator": "1",
+                "created": 1,
+                "consenter_ids": [1, 2, 3],
+                "waiver_budget": []
+            }"#,
+        )
+        .unwrap();
+        let usage = PlayerUsage::from_optimal_weeks(&[
+            (5, 2, "a", 10.0, true),
+            (5, 3, "b", 7.0, true),
+            (5, 2, "c", 25.0, true),
+        ]);
+
+        let analyses = calculate_trade_analyses(&[trade], &usage);
+        assert_eq!(analyses[0].sides[1].started_points, 35.0);
+        assert_eq!(
+            analyses[0].sides[1].received_points["a"].started_points,
+            10.0
+        );
+
+        let ledgers = calculate_trade_ledgers(&analyses);
+        let ledger = |roster_id| ledgers.iter().find(|l| l.roster_id == roster_id).unwrap();
+        assert_eq!(ledger(1).points_given_up, 10.0);
+        assert_eq!(ledger(2).points_given_up, 7.0);
+        assert_eq!(ledger(3).points_given_up, 25.0);
+        assert_eq!(ledger(2).net_points(), 28.0);
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::calculate_optimal_points::{lineup_points, optimal_lineup};
+use crate::models::{
+    matchup::Matchup, player::PlayerDetails, positions::RosterPosition, roster::RosterId,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WaiverWireStarter {
+    pub slot: RosterPosition,
+    pub player_id: String,
+    pub points: f32,
+}
+
+/// The best legal lineup built only from unrostered players for one week.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WaiverWireKing {
+    pub week: i32,
+    pub lineup: Vec<WaiverWireStarter>,
+    pub points: f32,
+    /// Rosters whose actual score that week was below the lineup's
+    pub teams_outscored: Vec<RosterId>,
+}
+
+/// Fills the league's starting slots from `pool`, the players no roster held
+/// that week, scored with `players_points`. Matchups only score rostered
+/// players, so `players_points` has to come from weekly player stats.
+pub fn calculate_waiver_wire_king(
+    week: i32,
+    pool: &[&PlayerDetails],
+    players_points: &HashMap<String, f32>,
+    league_positions: &[RosterPosition],
+    matchups: &[Matchup],
+) -> WaiverWireKing {
+    let lineup = optimal_lineup(league_positions, pool, players_points);
+    let points = lineup_points(&lineup, players_points);
+    let mut teams_outscored = matchups
+        .iter()
+        .filter(|m| m.points < points)
+        .map(|m| m.roster_id)
+        .collect::<Vec<_>>();
+    teams_outscored.sort();
+    WaiverWireKing {
+        week,
+        lineup: lineup
+            .into_iter()
+            .map(|(slot, player_id)| WaiverWireStarter {
+                slot,
+                points: players_points.get(&player_id).copied().unwrap_or_default(),
+                player_id,
+            })
+            .collect(),
+        points,
+        teams_outscored,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn player(player_id: &str, positions: &[&str]) -> PlayerDetails {
+        serde_json::from_value(serde_json::json!({
+            "player_id": player_id,
+            "active": true,
+            "sport": "nfl",
+            "fantasy_positions": positions
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn builds_best_unrostered_lineup() {
+        let pool = [
+            player("qb", &["QB"]),
+            player("rb1", &["RB"]),
+            player("rb2", &["RB"]),
+            player("wr", &["WR"]),
+            player("te", &["TE"]),
+        ];
+        let pool = pool.iter().collect::<Vec<_>>();
+        let players_points = [
+            ("qb", 18.0),
+            ("rb1", 15.0),
+            ("rb2", 12.0),
+            ("wr", 9.0),
+            ("te", 3.0),
+        ]
+        .into_iter()
+        .map(|(p, points)| (p.to_string(), points))
+        .collect::<HashMap<_, _>>();
+        let matchups: Vec<Matchup> =
+            serde_json::from_str(include_str!("../../fixtures/matchups_week_1.json")).unwrap();
+
+        let king = calculate_waiver_wire_king(
+            1,
+            &pool,
+            &players_points,
+            &[
+                RosterPosition::QB,
+                RosterPosition::RB,
+                RosterPosition::WR,
+                RosterPosition::FLEX,
+                RosterPosition::BN,
+            ],
+            &matchups,
+        );
+        assert_eq!(king.points, 54.0);
+        assert_eq!(king.lineup.len(), 4);
+        assert!(!king.lineup.iter().any(|s| s.player_id == "te"));
+        // 59.3 and 55.3 actual points
+        assert!(king.teams_outscored.is_empty());
+
+        let mut better = players_points.clone();
+        better.insert("te".to_string(), 14.0);
+        let king = calculate_waiver_wire_king(
+            1,
+            &pool,
+            &better,
+            &[
+                RosterPosition::QB,
+                RosterPosition::RB,
+                RosterPosition::WR,
+                RosterPosition::FLEX,
+            ],
+            &matchups,
+        );
+        assert_eq!(king.points, 56.0);
+        assert!(king.lineup.iter().any(|s| s.player_id == "te"));
+        assert_eq!(king.teams_outscored, vec![2]);
+    }
+}
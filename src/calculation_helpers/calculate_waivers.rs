@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::calculate_player_usage::PlayerUsage;
+use crate::models::{
+    roster::{Roster, RosterId},
+    transaction::{Transaction, TransactionType},
+};
+
+/// A player added off waivers or free agency.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Pickup {
+    pub transaction_id: String,
+    pub transaction_type: TransactionType,
+    pub week: i32,
+    pub player_id: String,
+    /// Winning FAAB bid, 0 for free agents
+    pub bid: u32,
+    /// [`PlayerUsage::started_points`] since the pickup
+    pub started_points: f32,
+    /// [`PlayerUsage::optimal_points`] since the pickup
+    pub optimal_points: f32,
+}
+
+/// A player dropped by a roster and started by others afterwards.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CostlyDrop {
+    pub transaction_id: String,
+    pub week: i32,
+    pub player_id: String,
+    /// Points other rosters started from the player since the drop
+    pub points_elsewhere: f32,
+}
+
+/// A roster's waiver and free agent activity over the season.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WaiverReport {
+    pub roster_id: RosterId,
+    /// League FAAB budget
+    pub waiver_budget: u32,
+    /// FAAB spent according to Sleeper, including bids on claims since undone
+    pub waiver_budget_used: u32,
+    /// Winning bids on the pickups below
+    pub winning_bids: u32,
+    /// Most started points first
+    pub pickups: Vec<Pickup>,
+    /// Most points elsewhere first, only drops someone else started
+    pub costly_drops: Vec<CostlyDrop>,
+}
+
+impl WaiverReport {
+    pub fn waiver_budget_remaining(&self) -> u32 {
+        self.waiver_budget.saturating_sub(self.waiver_budget_used)
+    }
+
+    pub fn started_points(&self) -> f32 {
+        self.pickups.iter().map(|p| p.started_points).sum()
+    }
+
+    pub fn optimal_points(&self) -> f32 {
+        self.pickups.iter().map(|p| p.optimal_points).sum()
+    }
+
+    /// Started points per FAAB dollar won with, `None` when nothing was spent.
+    pub fn started_points_per_dollar(&self) -> Option<f32> {
+        (self.winning_bids > 0).then(|| self.started_points() / self.winning_bids as f32)
+    }
+
+    pub fn optimal_points_per_dollar(&self) -> Option<f32> {
+        (self.winning_bids > 0).then(|| self.optimal_points() / self.winning_bids as f32)
+    }
+
+    pub fn best_pickup(&self) -> Option<&Pickup> {
+        self.pickups.first()
+    }
+
+    /// The paid pickup with the fewest started points per dollar, or the
+    /// free pickup with the fewest started points when nothing was paid for.
+    pub fn worst_pickup(&self) -> Option<&Pickup> {
+        let paid = self.pickups.iter().filter(|p| p.bid > 0).min_by(|a, b| {
+            (a.started_points / a.bid as f32)
+                .partial_cmp(&(b.started_points / b.bid as f32))
+                .unwrap()
+        });
+        paid.or(self.pickups.last())
+    }
+}
+
+/// Waiver reports for every roster, scoring pickups from the week they were
+/// claimed. Rosters with no pickups or drops are included so FAAB totals line up.
+pub fn calculate_waiver_reports(
+    transactions: &[Transaction],
+    usage: &PlayerUsage,
+    rosters: &[Roster],
+    waiver_budget: u32,
+) -> Vec<WaiverReport> {
+    let mut reports = rosters
+        .iter()
+        .map(|roster| {
+            (
+                roster.roster_id,
+                WaiverReport {
+                    roster_id: roster.roster_id,
+                    waiver_budget,
+                    waiver_budget_used: roster.settings.waiver_budget_used as u32,
+                    winning_bids: 0,
+                    pickups: vec![],
+                    costly_drops: vec![],
+                },
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+    let moves = transactions.iter().filter(|t| {
+        matches!(
+            t.transaction_type,
+            TransactionType::Waiver | TransactionType::FreeAgent
+        ) && t.is_complete()
+    });
+    for transaction in moves {
+        let bid = transaction
+            .settings
+            .as_ref()
+            .and_then(|s| s.waiver_bid)
+            .unwrap_or_default();
+        for roster_id in &transaction.roster_ids {
+            let Some(report) = reports.get_mut(roster_id) else {
+                continue;
+            };
+            let added = transaction.added_by(*roster_id);
+            // Sleeper puts the whole bid on the claim, not on each player
+            if !added.is_empty() {
+                report.winning_bids += bid;
+            }
+            for (idx, player_id) in added.into_iter().enumerate() {
+                report.pickups.push(Pickup {
+                    transaction_id: transaction.transaction_id.clone(),
+                    transaction_type: transaction.transaction_type,
+                    week: transaction.leg,
+                    bid: if idx == 0 { bid } else { 0 },
+                    started_points: usage.started_points(*roster_id, &player_id, transaction.leg),
+                    optimal_points: usage.optimal_points(*roster_id, &player_id, transaction.leg),
+                    player_id,
+                });
+            }
+            for player_id in transaction.dropped_by(*roster_id) {
+                let points_elsewhere =
+                    usage.started_points_elsewhere(*roster_id, &player_id, transaction.leg);
+                if points_elsewhere > 0.0 {
+                    report.costly_drops.push(CostlyDrop {
+                        transaction_id: transaction.transaction_id.clone(),
+                        week: transaction.leg,
+                        player_id,
+                        points_elsewhere,
+                    });
+                }
+            }
+        }
+    }
+    reports
+        .into_values()
+        .map(|mut report| {
+            report
+                .pickups
+                .sort_by(|a, b| b.started_points.partial_cmp(&a.started_points).unwrap());
+            report
+                .costly_drops
+                .sort_by(|a, b| b.points_elsewhere.partial_cmp(&a.points_elsewhere).unwrap());
+            report
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reports_waiver_activity() {
+        let rosters: Vec<Roster> =
+            serde_json::from_str(include_str!("../../fixtures/rosters.json")).unwrap();
+        let transactions: Vec<Transaction> =
+            serde_json::from_str(include_str!("../../fixtures/transactions.json")).unwrap();
+        let usage = PlayerUsage::from_optimal_weeks(&[
+            // Scored for roster 1 before the claim, not credited
+            (1, 1, "9999", 40.0, true),
+            (2, 1, "9999", 20.0, true),
+            (3, 1, "9999", 10.0, false),
+            // Dropped by roster 1 and picked up by roster 2
+            (3, 2, "8146", 18.0, true),
+            (4, 2, "8146", 6.0, false),
+        ]);
+
+        let reports = calculate_waiver_reports(&transactions, &usage, &rosters, 100);
+        assert_eq!(reports.len(), 2);
+        let report = &reports[0];
+        assert_eq!(report.winning_bids, 12);
+        assert_eq!(report.pickups.len(), 1);
+        assert_eq!(report.started_points(), 20.0);
+        assert_eq!(report.optimal_points(), 30.0);
+        assert_eq!(report.started_points_per_dollar(), Some(20.0 / 12.0));
+        assert_eq!(report.best_pickup(), report.worst_pickup());
+        assert_eq!(report.costly_drops.len(), 1);
+        assert_eq!(report.costly_drops[0].points_elsewhere, 18.0);
+        // The failed claim counts for nothing
+        assert_eq!(reports[1].winning_bids, 0);
+        assert!(reports[1].pickups.is_empty());
+        assert_eq!(reports[1].started_points_per_dollar(), None);
+        assert_eq!(
+            reports[1].waiver_budget_remaining(),
+            100 - reports[1].waiver_budget_used
+        );
+    }
+}
@@ -0,0 +1,348 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::calculate_optimal_points::{optimal_score_for_matchup, OptimalScoreForMatchup};
+use super::report::OwnerLabels;
+use crate::models::{
+    matchup::Matchup,
+    player::Players,
+    positions::RosterPosition,
+    roster::{Roster, RosterId},
+};
+
+/// The week's headlines, with managers and players already resolved to names
+/// so the recap can be rendered straight from a template.
+#[derive(Debug, Clone, Serialize)]
+pub struct WeeklyRecap {
+    pub week: i32,
+    pub bench_king: Option<RecapBenchKing>,
+    /// Every roster's points left on the bench, most first
+    pub benches: Vec<RecapBenchKing>,
+    pub highest_scorer: Option<RecapTeam>,
+    pub lowest_scorer: Option<RecapTeam>,
+    pub closest_game: Option<RecapGame>,
+    pub biggest_blowout: Option<RecapGame>,
+    pub best_performance: Option<RecapPerformance>,
+    pub biggest_blunder: Option<RecapBlunder>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecapTeam {
+    pub roster_id: RosterId,
+    pub manager: String,
+    pub points: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecapBenchKing {
+    pub roster_id: RosterId,
+    pub manager: String,
+    pub actual_points: f32,
+    pub optimal_points: f32,
+    pub points_left_on_bench: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecapGame {
+    pub matchup_id: u16,
+    pub winner: RecapTeam,
+    pub loser: RecapTeam,
+    pub margin: f32,
+}
+
+/// A single player's score for a roster that started them.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecapPerformance {
+    pub player_id: String,
+    pub player: String,
+    pub roster_id: RosterId,
+    pub manager: String,
+    pub points: f32,
+}
+
+/// The benched player who would have made the biggest difference, and the
+/// lowest scoring starter left out of the optimal lineup.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecapBlunder {
+    pub roster_id: RosterId,
+    pub manager: String,
+    pub benched_player: String,
+    pub benched_points: f32,
+    pub started_player: String,
+    pub started_points: f32,
+    pub points_lost: f32,
+}
+
+/// Fails when a matchup's roster isn't in `rosters`.
+pub fn calculate_weekly_recap(
+    week: i32,
+    matchups: &[Matchup],
+    rosters: &[Roster],
+    players: &Players,
+    league_positions: &[RosterPosition],
+    labels: &OwnerLabels,
+) -> Result<WeeklyRecap, String> {
+    let scores = matchups
+        .iter()
+        .map(|matchup| {
+            let roster = rosters
+                .iter()
+                .find(|r| r.roster_id == matchup.roster_id)
+                .ok_or(format!(
+                    "No roster {} for week {} matchup {}",
+                    matchup.roster_id, week, matchup.matchup_id
+                ))?;
+            Ok(optimal_score_for_matchup(
+                matchup.clone(),
+                roster.clone(),
+                players.clone(),
+                league_positions.to_vec(),
+            ))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(weekly_recap_from_scores(
+        week, matchups, &scores, players, labels,
+    ))
+}
+
+/// Builds the recap from optimal scores already calculated for each matchup.
+pub fn weekly_recap_from_scores(
+    week: i32,
+    matchups: &[Matchup],
+    scores: &[OptimalScoreForMatchup],
+    players: &Players,
+    labels: &OwnerLabels,
+) -> WeeklyRecap {
+    let manager = |roster_id: RosterId| {
+        let owner_id = scores
+            .iter()
+            .find(|s| s.roster_id == roster_id)
+            .map(|s| s.owner_id.as_str())
+            .unwrap_or_default();
+        labels.label(roster_id, owner_id)
+    };
+    let player_name = |player_id: &str| {
+        players
+            .get(player_id)
+            .map(|p| p.name())
+            .unwrap_or(player_id.to_string())
+    };
+    let team = |matchup: &Matchup| RecapTeam {
+        roster_id: matchup.roster_id,
+        manager: manager(matchup.roster_id),
+        points: matchup.points,
+    };
+
+    let mut benches = scores
+        .iter()
+        .map(|s| RecapBenchKing {
+            roster_id: s.roster_id,
+            manager: manager(s.roster_id),
+            actual_points: s.actual_points,
+            optimal_points: s.optimal_points,
+            points_left_on_bench: s.optimal_points - s.actual_points,
+        })
+        .collect::<Vec<_>>();
+    benches.sort_by(|a, b| {
+        b.points_left_on_bench
+            .partial_cmp(&a.points_left_on_bench)
+            .unwrap()
+    });
+    let bench_king = benches.first().cloned();
+    let highest_scorer = matchups
+        .iter()
+        .max_by(|a, b| a.points.partial_cmp(&b.points).unwrap())
+        .map(team);
+    let lowest_scorer = matchups
+        .iter()
+        .min_by(|a, b| a.points.partial_cmp(&b.points).unwrap())
+        .map(team);
+
+    let mut by_matchup: BTreeMap<u16, Vec<&Matchup>> = BTreeMap::new();
+    for matchup in matchups {
+        by_matchup
+            .entry(matchup.matchup_id)
+            .or_default()
+            .push(matchup);
+    }
+    let games = by_matchup
+        .into_iter()
+        .filter(|(_, sides)| sides.len() == 2)
+        .map(|(matchup_id, mut sides)| {
+            sides.sort_by(|a, b| b.points.partial_cmp(&a.points).unwrap());
+            RecapGame {
+                matchup_id,
+                winner: team(sides[0]),
+                loser: team(sides[1]),
+                margin: sides[0].points - sides[1].points,
+            }
+        })
+        .collect::<Vec<_>>();
+    let closest_game = games
+        .iter()
+        .min_by(|a, b| a.margin.partial_cmp(&b.margin).unwrap())
+        .cloned();
+    let biggest_blowout = games
+        .iter()
+        .max_by(|a, b| a.margin.partial_cmp(&b.margin).unwrap())
+        .cloned();
+
+    let best_performance = matchups
+        .iter()
+        .flat_map(|m| {
+            m.starters.iter().map(move |player_id| {
+                let points = m.players_points.get(player_id).copied().unwrap_or(0.0);
+                (m.roster_id, player_id, points)
+            })
+        })
+        .max_by(|a, b| a.2.partial_cmp(&b.2).unwrap())
+        .map(|(roster_id, player_id, points)| RecapPerformance {
+            player_id: player_id.clone(),
+            player: player_name(player_id),
+            roster_id,
+            manager: manager(roster_id),
+            points,
+        });
+
+    let biggest_blunder = matchups
+        .iter()
+        .filter_map(|m| {
+            let score = scores.iter().find(|s| s.roster_id == m.roster_id)?;
+            let points =
+                |player_id: &&String| m.players_points.get(*player_id).copied().unwrap_or(0.0);
+            let benched = score
+                .optimal_starters
+                .iter()
+                .filter(|p| !m.starters.contains(p))
+                .max_by(|a, b| points(a).partial_cmp(&points(b)).unwrap())?;
+            let started = m
+                .starters
+                .iter()
+                .filter(|p| !score.optimal_starters.contains(p))
+                .min_by(|a, b| points(a).partial_cmp(&points(b)).unwrap())?;
+            Some(RecapBlunder {
+                roster_id: m.roster_id,
+                manager: manager(m.roster_id),
+                benched_player: player_name(benched),
+                benched_points: points(&benched),
+                started_player: player_name(started),
+                started_points: points(&started),
+                points_lost: points(&benched) - points(&started),
+            })
+        })
+        .max_by(|a, b| a.points_lost.partial_cmp(&b.points_lost).unwrap());
+
+    WeeklyRecap {
+        week,
+        bench_king,
+        benches,
+        highest_scorer,
+        lowest_scorer,
+        closest_game,
+        biggest_blowout,
+        best_performance,
+        biggest_blunder,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn matchup(
+        matchup_id: u16,
+        roster_id: RosterId,
+        points: &[(&str, f32)],
+        starters: &[&str],
+    ) -> Matchup {
+        Matchup {
+            starters: starters.iter().map(|s| s.to_string()).collect(),
+            roster_id,
+            players: points.iter().map(|(p, _)| p.to_string()).collect(),
+            matchup_id,
+            points: starters
+                .iter()
+                .map(|s| points.iter().find(|(p, _)| p == s).unwrap().1)
+                .sum(),
+            custom_points: None,
+            players_points: points
+                .iter()
+                .map(|(p, pts)| (p.to_string(), *pts))
+                .collect(),
+            starters_points: vec![],
+        }
+    }
+
+    fn score(matchup: &Matchup, optimal_starters: &[&str]) -> OptimalScoreForMatchup {
+        OptimalScoreForMatchup {
+            matchup_id: matchup.matchup_id,
+            roster_id: matchup.roster_id,
+            owner_id: matchup.roster_id.to_string(),
+            actual_points: matchup.points,
+            optimal_points: optimal_starters
+                .iter()
+                .map(|p| matchup.players_points[*p])
+                .sum(),
+            optimal_starters: optimal_starters.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn recap_headlines() {
+        let matchups = vec![
+            matchup(1, 1, &[("a", 30.0), ("b", 2.0), ("c", 20.0)], &["a", "b"]),
+            matchup(1, 2, &[("d", 10.0), ("e", 12.0)], &["d", "e"]),
+            matchup(2, 3, &[("f", 40.0), ("g", 10.0)], &["f", "g"]),
+            matchup(2, 4, &[("h", 5.0), ("i", 6.0)], &["h", "i"]),
+        ];
+        let scores = vec![
+            score(&matchups[0], &["a", "c"]),
+            score(&matchups[1], &["d", "e"]),
+            score(&matchups[2], &["f", "g"]),
+            score(&matchups[3], &["h", "i"]),
+        ];
+        let recap = weekly_recap_from_scores(
+            3,
+            &matchups,
+            &scores,
+            &HashMap::new(),
+            &OwnerLabels::default(),
+        );
+        let bench_king = recap.bench_king.unwrap();
+        assert_eq!(bench_king.roster_id, 1);
+        assert_eq!(bench_king.points_left_on_bench, 18.0);
+        assert_eq!(recap.benches.len(), 4);
+        assert_eq!(recap.highest_scorer.unwrap().roster_id, 3);
+        assert_eq!(recap.lowest_scorer.unwrap().roster_id, 4);
+        let closest = recap.closest_game.unwrap();
+        assert_eq!((closest.winner.roster_id, closest.margin), (1, 10.0));
+        assert_eq!(recap.biggest_blowout.unwrap().matchup_id, 2);
+        assert_eq!(recap.best_performance.unwrap().player, "f");
+        let blunder = recap.biggest_blunder.unwrap();
+        assert_eq!(
+            (
+                blunder.benched_player.as_str(),
+                blunder.started_player.as_str()
+            ),
+            ("c", "b")
+        );
+        assert_eq!(blunder.points_lost, 18.0);
+    }
+
+    #[test]
+    fn missing_rosters_are_errors() {
+        let matchups = vec![matchup(4, 7, &[("a", 10.0)], &["a"])];
+        let recap = calculate_weekly_recap(
+            3,
+            &matchups,
+            &[],
+            &HashMap::new(),
+            &[RosterPosition::QB],
+            &OwnerLabels::default(),
+        );
+        assert_eq!(recap.unwrap_err(), "No roster 7 for week 3 matchup 4");
+    }
+}
@@ -1,27 +1,136 @@
-#[derive(Debug, Default, Clone)]
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::models::{
+    roster::RosterId,
+    user::{LeagueUser, UserId},
+};
+
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct Report {
-    pub owner_name: String,
+    pub week: i32,
+    pub roster_id: RosterId,
+    /// Owner of the roster for this week; resolve a display name with [OwnerLabels]
+    pub owner_id: UserId,
     pub optimal_points: f32,
     pub actual_points: f32,
+    /// Best score still reachable given lineup locks, when a schedule was available
+    pub achievable_points: Option<f32>,
 }
 impl Report {
+    /// A week's report for `roster_id` owned by `owner-{roster_id}`.
+    #[cfg(test)]
+    pub(crate) fn from_points(week: i32, roster_id: RosterId, actual: f32, optimal: f32) -> Report {
+        Report {
+            week,
+            roster_id,
+            owner_id: format!("owner-{}", roster_id),
+            optimal_points: optimal,
+            actual_points: actual,
+            achievable_points: None,
+        }
+    }
+
     pub fn difference(&self) -> f32 {
         self.optimal_points - self.actual_points
     }
+
+    /// Points left on the bench that the manager could still have started.
+    pub fn achievable_difference(&self) -> Option<f32> {
+        self.achievable_points.map(|a| a - self.actual_points)
+    }
+
+    /// Lineup efficiency, the share of the optimal score that was actually started.
+    /// A week with no optimal points counts as perfectly efficient.
+    pub fn efficiency(&self) -> f32 {
+        if self.optimal_points <= 0.0 {
+            return 1.0;
+        }
+        self.actual_points / self.optimal_points
+    }
+
+    pub fn describe(&self, labels: &OwnerLabels) -> String {
+        self.describe_as(&labels.label(self.roster_id, &self.owner_id))
+    }
+
+    fn describe_as(&self, label: &str) -> String {
+        let mut description = format!(
+            "Owner {} scored {} points, optimal score is {}, difference is {}, efficiency is {}%",
+            label,
+            round_to_two_decimals(self.actual_points),
+            round_to_two_decimals(self.optimal_points),
+            round_to_two_decimals(self.difference()),
+            round_to_two_decimals(self.efficiency() * 100.0)
+        );
+        if let (Some(achievable), Some(difference)) =
+            (self.achievable_points, self.achievable_difference())
+        {
+            description.push_str(&format!(
+                ", achievable score is {}, achievable difference is {}",
+                round_to_two_decimals(achievable),
+                round_to_two_decimals(difference)
+            ));
+        }
+        description
+    }
 }
 impl std::fmt::Display for Report {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Owner {} scored {} points, optimal score is {}, difference is {}",
-            self.owner_name,
-            round_to_two_decimals(self.actual_points),
-            round_to_two_decimals(self.optimal_points),
-            round_to_two_decimals(self.difference())
-        )
+        write!(f, "{}", self.describe_as(&roster_label(self.roster_id)))
+    }
+}
+
+/// Resolves owner ids to display names at render time. Display names shared by
+/// more than one owner are suffixed with the roster id so they stay distinguishable.
+#[derive(Debug, Default, Clone)]
+pub struct OwnerLabels {
+    names: HashMap<UserId, String>,
+    duplicated: HashSet<String>,
+}
+
+impl OwnerLabels {
+    pub fn new(owners: &[LeagueUser]) -> OwnerLabels {
+        OwnerLabels::from_names(owners.iter().map(|o| (&o.user_id, o.display_name.clone())))
+    }
+
+    /// Labels owners by team name, falling back to display name for owners
+    /// who haven't named their team.
+    pub fn with_team_names(owners: &[LeagueUser]) -> OwnerLabels {
+        OwnerLabels::from_names(owners.iter().map(|o| {
+            let team_name = o.metadata.team_name.clone();
+            (&o.user_id, team_name.unwrap_or(o.display_name.clone()))
+        }))
+    }
+
+    fn from_names<'a>(names_by_owner: impl Iterator<Item = (&'a UserId, String)>) -> OwnerLabels {
+        let mut names = HashMap::new();
+        let mut seen = HashSet::new();
+        let mut duplicated = HashSet::new();
+        for (owner_id, name) in names_by_owner {
+            if !seen.insert(name.clone()) {
+                duplicated.insert(name.clone());
+            }
+            names.insert(owner_id.clone(), name);
+        }
+        OwnerLabels { names, duplicated }
+    }
+
+    pub fn label(&self, roster_id: RosterId, owner_id: &str) -> String {
+        match self.names.get(owner_id) {
+            Some(name) if self.duplicated.contains(name) => {
+                format!("{} ({})", name, roster_label(roster_id))
+            }
+            Some(name) => name.clone(),
+            None => roster_label(roster_id),
+        }
     }
 }
 
-fn round_to_two_decimals(f: f32) -> f32 {
+fn roster_label(roster_id: RosterId) -> String {
+    format!("Roster {}", roster_id)
+}
+
+pub fn round_to_two_decimals(f: f32) -> f32 {
     (f * 100.0).round() / 100.0
-}
\ No newline at end of file
+}
@@ -0,0 +1,337 @@
+//! Dependency free SVG line and bar charts, sized for embedding inline in the
+//! dashboard and recap output.
+
+use crate::calculation_helpers::calculate_efficiency::SeasonSummary;
+use crate::calculation_helpers::report::{OwnerLabels, Report};
+
+const WIDTH: f32 = 640.0;
+const HEIGHT: f32 = 320.0;
+const MARGIN_TOP: f32 = 30.0;
+const MARGIN_RIGHT: f32 = 150.0;
+const MARGIN_BOTTOM: f32 = 40.0;
+const MARGIN_LEFT: f32 = 50.0;
+const Y_TICKS: usize = 5;
+const PALETTE: [&str; 10] = [
+    "#2a7ae2", "#e2662a", "#2ab24a", "#c23b3b", "#8a56c2", "#8c6d3f", "#d463b5", "#7f7f7f",
+    "#b5b82a", "#2ab8c2",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    pub name: String,
+    /// One value per chart category
+    pub values: Vec<f32>,
+}
+
+/// Series plotted against shared categories, e.g. weeks. Values are formatted
+/// as percentages when `percent` is set, so pass efficiencies as fractions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chart {
+    pub title: String,
+    pub categories: Vec<String>,
+    pub series: Vec<Series>,
+    pub percent: bool,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Rounds `value` up to 1, 2 or 5 times a power of ten, for readable axis ticks.
+fn nice_ceiling(value: f32) -> f32 {
+    if value <= 0.0 {
+        return 1.0;
+    }
+    let magnitude = 10f32.powf(value.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|m| m * magnitude)
+        .find(|v| *v >= value)
+        .unwrap_or(10.0 * magnitude)
+}
+
+impl Chart {
+    fn values(&self) -> impl Iterator<Item = f32> + '_ {
+        self.series.iter().flat_map(|s| s.values.iter().copied())
+    }
+
+    /// The y axis range, always including zero.
+    fn y_range(&self) -> (f32, f32) {
+        let max = self.values().fold(0.0, f32::max);
+        let min = self.values().fold(0.0, f32::min);
+        let max = if self.percent && max <= 1.0 {
+            1.0
+        } else {
+            nice_ceiling(max)
+        };
+        let min = if min < 0.0 { -nice_ceiling(-min) } else { 0.0 };
+        (min, max)
+    }
+
+    fn y(&self, value: f32) -> f32 {
+        let (min, max) = self.y_range();
+        let plot_height = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+        HEIGHT - MARGIN_BOTTOM - (value - min) / (max - min) * plot_height
+    }
+
+    fn category_width(&self) -> f32 {
+        (WIDTH - MARGIN_LEFT - MARGIN_RIGHT) / self.categories.len().max(1) as f32
+    }
+
+    fn x(&self, category: usize) -> f32 {
+        MARGIN_LEFT + (category as f32 + 0.5) * self.category_width()
+    }
+
+    fn tick_label(&self, value: f32) -> String {
+        if self.percent {
+            format!("{:.0}%", value * 100.0)
+        } else {
+            format!("{:.0}", value)
+        }
+    }
+
+    /// Title, axes, gridlines, category labels and legend.
+    fn frame(&self) -> Vec<String> {
+        let mut elements = vec![
+            format!(
+                r##"<rect width="{}" height="{}" fill="#ffffff"/>"##,
+                WIDTH, HEIGHT
+            ),
+            format!(
+                r#"<text x="{}" y="20" font-size="14" font-weight="bold">{}</text>"#,
+                MARGIN_LEFT,
+                escape(&self.title)
+            ),
+        ];
+        let (min, max) = self.y_range();
+        for tick in 0..=Y_TICKS {
+            let value = min + (max - min) * tick as f32 / Y_TICKS as f32;
+            let y = self.y(value);
+            elements.push(format!(
+                r##"<line x1="{}" y1="{y:.1}" x2="{}" y2="{y:.1}" stroke="#e0e0e0"/><text x="{}" y="{:.1}" font-size="10" text-anchor="end">{}</text>"##,
+                MARGIN_LEFT,
+                WIDTH - MARGIN_RIGHT,
+                MARGIN_LEFT - 6.0,
+                y + 3.0,
+                self.tick_label(value)
+            ));
+        }
+        // Roughly 6px per character at font size 10
+        let max_chars = ((self.category_width() / 6.0) as usize).max(3);
+        for (idx, category) in self.categories.iter().enumerate() {
+            let label = if category.chars().count() > max_chars {
+                format!(
+                    "{}…",
+                    category.chars().take(max_chars - 1).collect::<String>()
+                )
+            } else {
+                category.clone()
+            };
+            elements.push(format!(
+                r#"<text x="{:.1}" y="{}" font-size="10" text-anchor="middle"><title>{}</title>{}</text>"#,
+                self.x(idx),
+                HEIGHT - MARGIN_BOTTOM + 14.0,
+                escape(category),
+                escape(&label)
+            ));
+        }
+        for (idx, series) in self.series.iter().enumerate() {
+            let y = MARGIN_TOP + 16.0 * idx as f32;
+            elements.push(format!(
+                r#"<rect x="{}" y="{}" width="10" height="10" fill="{}"/><text x="{}" y="{}" font-size="11">{}</text>"#,
+                WIDTH - MARGIN_RIGHT + 12.0,
+                y,
+                PALETTE[idx % PALETTE.len()],
+                WIDTH - MARGIN_RIGHT + 26.0,
+                y + 9.0,
+                escape(&series.name)
+            ));
+        }
+        elements
+    }
+
+    fn svg(&self, elements: Vec<String>) -> String {
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {w} {h}" width="{w}" height="{h}" font-family="sans-serif" role="img" aria-label="{title}">{elements}</svg>"#,
+            w = WIDTH,
+            h = HEIGHT,
+            title = escape(&self.title),
+            elements = elements.concat()
+        )
+    }
+
+    pub fn line_svg(&self) -> String {
+        let mut elements = self.frame();
+        for (idx, series) in self.series.iter().enumerate() {
+            let coordinates = series
+                .values
+                .iter()
+                .enumerate()
+                .map(|(category, value)| format!("{:.1},{:.1}", self.x(category), self.y(*value)))
+                .collect::<Vec<_>>();
+            elements.push(format!(
+                r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2"/>"#,
+                coordinates.join(" "),
+                PALETTE[idx % PALETTE.len()]
+            ));
+        }
+        self.svg(elements)
+    }
+
+    /// Grouped bars, one group per category.
+    pub fn bar_svg(&self) -> String {
+        let mut elements = self.frame();
+        let group_width = self.category_width() * 0.8;
+        let bar_width = group_width / self.series.len().max(1) as f32;
+        let zero = self.y(0.0);
+        for (idx, series) in self.series.iter().enumerate() {
+            for (category, value) in series.values.iter().enumerate() {
+                let x = self.x(category) - group_width / 2.0 + idx as f32 * bar_width;
+                let y = self.y(*value);
+                elements.push(format!(
+                    r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}"><title>{}: {:.2}</title></rect>"#,
+                    x,
+                    y.min(zero),
+                    bar_width,
+                    (zero - y).abs(),
+                    PALETTE[idx % PALETTE.len()],
+                    escape(&series.name),
+                    value
+                ));
+            }
+        }
+        self.svg(elements)
+    }
+}
+
+fn week_categories(weeks: &[Report]) -> Vec<String> {
+    weeks.iter().map(|r| format!("Wk {}", r.week)).collect()
+}
+
+/// Weekly optimal and actual points for one manager, as a bar chart.
+pub fn optimal_vs_actual_chart(title: &str, weeks: &[Report]) -> Chart {
+    Chart {
+        title: title.to_string(),
+        categories: week_categories(weeks),
+        series: vec![
+            Series {
+                name: "Optimal".to_string(),
+                values: weeks.iter().map(|r| r.optimal_points).collect(),
+            },
+            Series {
+                name: "Actual".to_string(),
+                values: weeks.iter().map(|r| r.actual_points).collect(),
+            },
+        ],
+        percent: false,
+    }
+}
+
+/// Weekly efficiency with its least-squares trend line, as a line chart.
+pub fn efficiency_trend_chart(title: &str, summary: &SeasonSummary) -> Chart {
+    let slope = summary.efficiency_trend();
+    let n = summary.weeks.len().max(1) as f32;
+    let mean_week = summary.weeks.iter().map(|r| r.week as f32).sum::<f32>() / n;
+    let intercept = summary.mean_weekly_efficiency() - slope * mean_week;
+    Chart {
+        title: title.to_string(),
+        categories: week_categories(&summary.weeks),
+        series: vec![
+            Series {
+                name: "Efficiency".to_string(),
+                values: summary.weeks.iter().map(|r| r.efficiency()).collect(),
+            },
+            Series {
+                name: "Trend".to_string(),
+                values: summary
+                    .weeks
+                    .iter()
+                    .map(|r| intercept + slope * r.week as f32)
+                    .collect(),
+            },
+        ],
+        percent: true,
+    }
+}
+
+/// Running total of points left on the bench, one line per manager. Weeks a
+/// manager has no report for carry their total forward.
+pub fn cumulative_bench_chart(
+    title: &str,
+    summaries: &[SeasonSummary],
+    labels: &OwnerLabels,
+) -> Chart {
+    let mut weeks = summaries
+        .iter()
+        .flat_map(|s| s.weeks.iter().map(|r| r.week))
+        .collect::<Vec<_>>();
+    weeks.sort();
+    weeks.dedup();
+    Chart {
+        title: title.to_string(),
+        categories: weeks.iter().map(|w| format!("Wk {}", w)).collect(),
+        series: summaries
+            .iter()
+            .map(|s| {
+                let mut total = 0.0;
+                Series {
+                    name: labels.label(s.roster_id, &s.owner_id()),
+                    values: weeks
+                        .iter()
+                        .map(|week| {
+                            total += s
+                                .weeks
+                                .iter()
+                                .find(|r| r.week == *week)
+                                .map(|r| r.difference())
+                                .unwrap_or_default();
+                            total
+                        })
+                        .collect(),
+                }
+            })
+            .collect(),
+        percent: false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn renders_line_and_bar_charts() {
+        assert_eq!(nice_ceiling(37.0), 50.0);
+        assert_eq!(nice_ceiling(120.0), 200.0);
+        assert_eq!(nice_ceiling(0.0), 1.0);
+
+        let chart = Chart {
+            title: "Bench <Points>".to_string(),
+            categories: vec!["Wk 1".to_string(), "Wk 2".to_string()],
+            series: vec![
+                Series {
+                    name: "Tom & Jerry".to_string(),
+                    values: vec![10.0, 30.0],
+                },
+                Series {
+                    name: "Other".to_string(),
+                    values: vec![5.0, 40.0],
+                },
+            ],
+            percent: false,
+        };
+        assert_eq!(chart.y_range(), (0.0, 50.0));
+        let line = chart.line_svg();
+        assert!(line.starts_with("<svg"));
+        assert_eq!(line.matches("<polyline").count(), 2);
+        assert!(line.contains("Tom &amp; Jerry"));
+        assert!(line.contains("Bench &lt;Points&gt;"));
+        // Wk 2 for the first series sits at 30 of 50 on a 250px tall plot
+        assert!(line.contains("380.0,130.0"));
+        let bar = chart.bar_svg();
+        assert_eq!(bar.matches("<rect").count(), 1 + 2 + 4);
+    }
+}
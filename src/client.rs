@@ -3,22 +3,27 @@ use std::time::Duration;
 use http_cache_reqwest::{
     CACacheManager, Cache, CacheMode, CacheOptions, HttpCache, HttpCacheOptions,
 };
-use reqwest::{Client, Response};
+use reqwest::{Client, Response, StatusCode};
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 use serde_json::Deserializer;
 
 use crate::models::{
+    draft::{Draft, DraftPick},
     league::League,
     matchup::Matchup,
     player::{Players, TrendingPlayer},
     playoff::Playoff,
     roster::Roster,
+    state::State,
+    stats::{PlayerProjection, PlayerWeekStats, StatLines},
+    transaction::Transaction,
     user::{LeagueUser, User},
 };
 
-const BASE_URL: &'static str = "https://api.sleeper.app/v1/";
+const BASE_URL: &str = "https://api.sleeper.app/v1/";
 pub struct SleeperClient {
     pub client: ClientWithMiddleware,
+    base_url: String,
 }
 
 pub enum AvatarType {
@@ -26,10 +31,11 @@ pub enum AvatarType {
     Thumb,
 }
 
+#[allow(dead_code)]
 struct NotImplementedError;
 impl std::fmt::Display for NotImplementedError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", "Not implemented yet")
+        write!(f, "Not implemented yet")
     }
 }
 pub struct Sport(pub String);
@@ -49,6 +55,46 @@ pub enum ActionType {
     Drop,
 }
 
+/// Longest lookback accepted for trending players, one week.
+pub const MAX_TRENDING_LOOKBACK_HOURS: u32 = 168;
+/// Most trending players returned in one request.
+pub const MAX_TRENDING_LIMIT: u32 = 200;
+
+fn trending_players_url(
+    base_url: &str,
+    action_type: ActionType,
+    sport: Option<Sport>,
+    lookback_hours: Option<u32>,
+    limit: Option<u32>,
+) -> Result<String, String> {
+    let lookback_hours = lookback_hours.unwrap_or(24);
+    if !(1..=MAX_TRENDING_LOOKBACK_HOURS).contains(&lookback_hours) {
+        return Err(format!(
+            "Lookback hours must be between 1 and {}, got {}",
+            MAX_TRENDING_LOOKBACK_HOURS, lookback_hours
+        ));
+    }
+    let limit = limit.unwrap_or(25);
+    if !(1..=MAX_TRENDING_LIMIT).contains(&limit) {
+        return Err(format!(
+            "Limit must be between 1 and {}, got {}",
+            MAX_TRENDING_LIMIT, limit
+        ));
+    }
+    let action_type = match action_type {
+        ActionType::Add => "add",
+        ActionType::Drop => "drop",
+    };
+    Ok(format!(
+        "{}players/{}/trending/{}?lookback_hours={}&limit={}",
+        base_url,
+        sport.unwrap_or_default(),
+        action_type,
+        lookback_hours,
+        limit
+    ))
+}
+
 pub enum WinnerOrLoser {
     Winner,
     Loser,
@@ -56,6 +102,11 @@ pub enum WinnerOrLoser {
 
 impl SleeperClient {
     pub fn build() -> SleeperClient {
+        SleeperClient::build_with_cache_dir(CACacheManager::default().path)
+    }
+
+    /// Builds a client that keeps its HTTP cache in `cache_dir`.
+    pub fn build_with_cache_dir(cache_dir: impl Into<std::path::PathBuf>) -> SleeperClient {
         // TODO: Figure out how to have a different cache policy for different endpoints
         let cache_options = CacheOptions {
             immutable_min_time_to_live: Duration::from_secs(86400),
@@ -64,7 +115,9 @@ impl SleeperClient {
             cache_heuristic: 0.1,
         };
         let cache = Cache(HttpCache {
-            manager: CACacheManager::default(),
+            manager: CACacheManager {
+                path: cache_dir.into(),
+            },
             mode: CacheMode::Default,
             options: HttpCacheOptions {
                 cache_options: Some(cache_options),
@@ -74,11 +127,24 @@ impl SleeperClient {
             },
         });
         let client = ClientBuilder::new(Client::new()).with(cache).build();
-        SleeperClient { client }
+        SleeperClient {
+            client,
+            base_url: BASE_URL.to_string(),
+        }
+    }
+
+    /// Points the client at another Sleeper compatible API, e.g. a local stand-in
+    /// serving recorded responses. `base_url` should end with a slash.
+    pub fn with_base_url(self, base_url: impl Into<String>) -> SleeperClient {
+        SleeperClient {
+            base_url: base_url.into(),
+            ..self
+        }
     }
 
+    /// Looks up a user by user id or username.
     pub async fn get_user(&self, user_id: String) -> Result<User, String> {
-        let url = format!("{BASE_URL}user/{}", user_id);
+        let url = format!("{}user/{}", self.base_url, user_id);
         match self.get_url(url).await {
             Ok(user) => Ok(user),
             Err(e) => Err(e),
@@ -90,7 +156,7 @@ impl SleeperClient {
             AvatarType::Full => "",
             AvatarType::Thumb => "thumb",
         };
-        format!("{BASE_URL}avatars/{}/{}", full_or_thumb, avatar_id)
+        format!("{}avatars/{}/{}", self.base_url, full_or_thumb, avatar_id)
     }
 
     pub async fn get_all_leagues_for_user(
@@ -100,9 +166,10 @@ impl SleeperClient {
         sport: Option<Sport>,
     ) -> Result<Vec<League>, String> {
         let url = format!(
-            "{BASE_URL}user/{}/leagues/{}/{}",
+            "{}user/{}/leagues/{}/{}",
+            self.base_url,
             user_id,
-            sport.unwrap_or(Sport::default()),
+            sport.unwrap_or_default(),
             season
         );
         match self.get_url(url).await {
@@ -112,15 +179,29 @@ impl SleeperClient {
     }
 
     pub async fn get_league_details(&self, league_id: String) -> Result<League, String> {
-        let url = format!("{BASE_URL}league/{}", league_id);
-        match self.get_url(url).await {
-            Ok(league) => Ok(league),
+        match self.find_league(league_id.clone()).await {
+            Ok(Some(league)) => Ok(league),
+            Ok(None) => Err(format!("League {} not found", league_id)),
             Err(e) => Err(e),
         }
     }
 
+    /// League details, or `None` when Sleeper doesn't know the league. Sleeper
+    /// answers unknown league ids with `null` rather than a 404.
+    pub async fn find_league(&self, league_id: String) -> Result<Option<League>, String> {
+        let url = format!("{}league/{}", self.base_url, league_id);
+        let response = match self.client.get(&url).send().await {
+            Ok(response) => response,
+            Err(e) => return Err(e.to_string()),
+        };
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        self.get_err_path(response).await
+    }
+
     pub async fn get_rosters_in_league(&self, league_id: String) -> Result<Vec<Roster>, String> {
-        let url = format!("{BASE_URL}league/{}/rosters", league_id);
+        let url = format!("{}league/{}/rosters", self.base_url, league_id);
         match self.get_url(url).await {
             Ok(rosters) => Ok(rosters),
             Err(e) => Err(e),
@@ -128,7 +209,7 @@ impl SleeperClient {
     }
 
     pub async fn get_users_in_league(&self, league_id: String) -> Result<Vec<LeagueUser>, String> {
-        let url = format!("{BASE_URL}league/{}/users", league_id);
+        let url = format!("{}league/{}/users", self.base_url, league_id);
         match self.get_url(url).await {
             Ok(users) => Ok(users),
             Err(e) => Err(e),
@@ -140,13 +221,78 @@ impl SleeperClient {
         league_id: String,
         week: i32,
     ) -> Result<Vec<Matchup>, String> {
-        let url = format!("{BASE_URL}league/{}/matchups/{}", league_id, week);
+        let url = format!("{}league/{}/matchups/{}", self.base_url, league_id, week);
         match self.get_url(url).await {
             Ok(matchups) => Ok(matchups),
             Err(e) => Err(e),
         }
     }
 
+    pub async fn get_state(&self, sport: Option<Sport>) -> Result<State, String> {
+        let url = format!("{}state/{}", self.base_url, sport.unwrap_or_default());
+        match self.get_url(url).await {
+            Ok(state) => Ok(state),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Transactions for a round, which is the week in football.
+    pub async fn get_transactions(
+        &self,
+        league_id: String,
+        round: i32,
+    ) -> Result<Vec<Transaction>, String> {
+        let url = format!(
+            "{}league/{}/transactions/{}",
+            self.base_url, league_id, round
+        );
+        match self.get_url(url).await {
+            Ok(transactions) => Ok(transactions),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Transactions for rounds 1 through `last_round`, fetched concurrently.
+    pub async fn get_transactions_through(
+        &self,
+        league_id: String,
+        last_round: i32,
+    ) -> Result<Vec<Transaction>, String> {
+        let rounds = futures::future::join_all(
+            (1..=last_round).map(|round| self.get_transactions(league_id.clone(), round)),
+        )
+        .await;
+        let mut transactions = vec![];
+        for round in rounds {
+            transactions.extend(round?);
+        }
+        Ok(transactions)
+    }
+
+    pub async fn get_drafts_for_league(&self, league_id: String) -> Result<Vec<Draft>, String> {
+        let url = format!("{}league/{}/drafts", self.base_url, league_id);
+        match self.get_url(url).await {
+            Ok(drafts) => Ok(drafts),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn get_draft(&self, draft_id: String) -> Result<Draft, String> {
+        let url = format!("{}draft/{}", self.base_url, draft_id);
+        match self.get_url(url).await {
+            Ok(draft) => Ok(draft),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn get_draft_picks(&self, draft_id: String) -> Result<Vec<DraftPick>, String> {
+        let url = format!("{}draft/{}/picks", self.base_url, draft_id);
+        match self.get_url(url).await {
+            Ok(picks) => Ok(picks),
+            Err(e) => Err(e),
+        }
+    }
+
     // WIP: Need to figure out how deserialize t1_from/t2_from
     pub async fn get_playoff_bracket_for_league(
         &self,
@@ -155,8 +301,10 @@ impl SleeperClient {
     ) -> Result<Vec<Playoff>, String> {
         //return Err(NotImplementedError.to_string());
         let url = match winner_or_loser {
-            WinnerOrLoser::Winner => format!("{BASE_URL}league/{}/winners_bracket", league_id),
-            WinnerOrLoser::Loser => format!("{BASE_URL}league/{}/losers_bracket", league_id),
+            WinnerOrLoser::Winner => {
+                format!("{}league/{}/winners_bracket", self.base_url, league_id)
+            }
+            WinnerOrLoser::Loser => format!("{}league/{}/losers_bracket", self.base_url, league_id),
         };
         match self.get_url(url).await {
             Ok(bracket) => Ok(bracket),
@@ -165,32 +313,71 @@ impl SleeperClient {
     }
 
     pub async fn fetch_all_players(&self) -> Result<Players, String> {
-        let url = format!("{BASE_URL}players/nfl");
+        let url = format!("{}players/nfl", self.base_url);
         match self.get_url(url).await {
             Ok(players) => Ok(players),
             Err(e) => Err(e),
         }
     }
 
+    /// Most added or dropped players across Sleeper. `lookback_hours` defaults
+    /// to 24 and `limit` to 25.
     pub async fn get_trending_players(
         &self,
         action_type: ActionType,
         sport: Option<Sport>,
-        lookback_hours: Option<String>,
-        limit: Option<String>,
+        lookback_hours: Option<u32>,
+        limit: Option<u32>,
     ) -> Result<Vec<TrendingPlayer>, String> {
-        let action_type_str = match action_type {
-            ActionType::Add => "add",
-            ActionType::Drop => "drop",
-        };
-        let url = format!("{BASE_URL}players/{sport}/trending/{type}?lookback_hours={lookback_hours}&limit={limit}", sport=sport.unwrap_or(Sport::default()), 
-        type=action_type_str, lookback_hours=lookback_hours.unwrap_or("24".to_string()), limit=limit.unwrap_or("25".to_string()));
+        let url = trending_players_url(&self.base_url, action_type, sport, lookback_hours, limit)?;
         match self.get_url(url).await {
             Ok(players) => Ok(players),
             Err(e) => Err(e),
         }
     }
 
+    /// Regular season stats for every player in a week. The endpoint is
+    /// unofficial, so it may change without notice.
+    pub async fn get_player_stats_for_week(
+        &self,
+        sport: Option<Sport>,
+        season: String,
+        week: i32,
+    ) -> Result<Vec<PlayerWeekStats>, String> {
+        let url = format!(
+            "{}stats/{}/regular/{}/{}",
+            self.base_url,
+            sport.unwrap_or_default(),
+            season,
+            week
+        );
+        match self.get_url::<StatLines>(url).await {
+            Ok(lines) => Ok(PlayerWeekStats::from_stat_lines(&season, week, lines)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Regular season projections for every player in a week. The endpoint is
+    /// unofficial, so it may change without notice.
+    pub async fn get_player_projections_for_week(
+        &self,
+        sport: Option<Sport>,
+        season: String,
+        week: i32,
+    ) -> Result<Vec<PlayerProjection>, String> {
+        let url = format!(
+            "{}projections/{}/regular/{}/{}",
+            self.base_url,
+            sport.unwrap_or_default(),
+            season,
+            week
+        );
+        match self.get_url::<StatLines>(url).await {
+            Ok(lines) => Ok(PlayerProjection::from_stat_lines(&season, week, lines)),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Helper method to debug struct path errors
     async fn get_err_path<T>(&self, response: Response) -> Result<T, String>
     where
@@ -229,3 +416,87 @@ impl SleeperClient {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    use super::*;
+
+    /// Answers a single request with `body`, standing in for the Sleeper API.
+    /// Returns the client's base URL and a handle with the request line.
+    fn sleeper_stand_in(body: &'static str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            request_line.trim_end().to_string()
+        });
+        (base_url, handle)
+    }
+
+    fn client(base_url: String) -> SleeperClient {
+        SleeperClient::build_with_cache_dir(std::env::temp_dir().join("bench-king-client-test"))
+            .with_base_url(base_url)
+    }
+
+    #[tokio::test]
+    async fn gets_player_stats_and_projections() {
+        let (base_url, handle) = sleeper_stand_in(include_str!("../fixtures/stats_week_1.json"));
+        let stats = client(base_url)
+            .get_player_stats_for_week(None, "2024".to_string(), 1)
+            .await
+            .unwrap();
+        assert_eq!(
+            handle.join().unwrap(),
+            "GET /stats/nfl/regular/2024/1 HTTP/1.1"
+        );
+        assert_eq!(stats.len(), 4);
+        assert_eq!(stats[0].player_id, "4046");
+        assert_eq!(stats[0].week, 1);
+        assert_eq!(stats[0].stats.pass_yd, Some(310.0));
+
+        let (base_url, handle) =
+            sleeper_stand_in(include_str!("../fixtures/projections_week_1.json"));
+        let projections = client(base_url)
+            .get_player_projections_for_week(Some(Sport("nfl".to_string())), "2024".to_string(), 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            handle.join().unwrap(),
+            "GET /projections/nfl/regular/2024/2 HTTP/1.1"
+        );
+        assert_eq!(projections.len(), 2);
+        assert_eq!(projections[0].season, "2024");
+        assert_eq!(projections[0].week, 2);
+    }
+
+    #[test]
+    fn validates_trending_parameters() {
+        assert_eq!(
+            trending_players_url(BASE_URL, ActionType::Drop, None, None, Some(10)).unwrap(),
+            "https://api.sleeper.app/v1/players/nfl/trending/drop?lookback_hours=24&limit=10"
+        );
+        assert!(trending_players_url(BASE_URL, ActionType::Add, None, Some(0), None).is_err());
+        assert!(trending_players_url(BASE_URL, ActionType::Add, None, Some(169), None).is_err());
+        assert!(trending_players_url(BASE_URL, ActionType::Add, None, None, Some(201)).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+//! Static HTML dashboard for a league season: a leaderboard index, a page per
+//! week and a page per manager. Pages only link to each other, so the output
+//! directory can be served from any plain file server.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use handlebars::{handlebars_helper, Handlebars};
+use serde_json::{json, Value};
+
+use crate::calculation_helpers::calculate_efficiency::calculate_efficiency_leaderboard;
+use crate::calculation_helpers::report::{OwnerLabels, Report};
+use crate::charts::{cumulative_bench_chart, efficiency_trend_chart, optimal_vs_actual_chart};
+use crate::models::roster::RosterId;
+use crate::recap::points;
+
+const LAYOUT_TEMPLATE: &str = include_str!("../templates/dashboard/layout.html.hbs");
+const INDEX_TEMPLATE: &str = include_str!("../templates/dashboard/index.html.hbs");
+const WEEK_TEMPLATE: &str = include_str!("../templates/dashboard/week.html.hbs");
+const MANAGER_TEMPLATE: &str = include_str!("../templates/dashboard/manager.html.hbs");
+
+handlebars_helper!(percent: |value: f64| format!("{:.2}%", value * 100.0));
+
+/// A rendered page, `file_name` is relative to the dashboard directory.
+#[derive(Debug, Clone)]
+pub struct DashboardPage {
+    pub file_name: String,
+    pub html: String,
+}
+
+fn manager_page(roster_id: RosterId) -> String {
+    format!("manager-{}.html", roster_id)
+}
+
+fn report_row(report: &Report, labels: &OwnerLabels) -> Value {
+    json!({
+        "week": report.week,
+        "roster_id": report.roster_id,
+        "owner": labels.label(report.roster_id, &report.owner_id),
+        "page": manager_page(report.roster_id),
+        "actual_points": report.actual_points,
+        "optimal_points": report.optimal_points,
+        "difference": report.difference(),
+        "efficiency": report.efficiency(),
+    })
+}
+
+/// Renders every page of the dashboard from weekly `reports`.
+pub fn render_dashboard(
+    league_name: &str,
+    season: &str,
+    reports: &[Report],
+    labels: &OwnerLabels,
+) -> Result<Vec<DashboardPage>, String> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("points", Box::new(points));
+    handlebars.register_helper("percent", Box::new(percent));
+    for (name, template) in [
+        ("layout", LAYOUT_TEMPLATE),
+        ("index", INDEX_TEMPLATE),
+        ("week", WEEK_TEMPLATE),
+        ("manager", MANAGER_TEMPLATE),
+    ] {
+        handlebars
+            .register_template_string(name, template)
+            .map_err(|e| format!("Invalid dashboard template {}: {}", name, e))?;
+    }
+
+    let mut by_week: BTreeMap<i32, Vec<&Report>> = BTreeMap::new();
+    for report in reports {
+        by_week.entry(report.week).or_default().push(report);
+    }
+    for week in by_week.values_mut() {
+        week.sort_by(|a, b| b.difference().partial_cmp(&a.difference()).unwrap());
+    }
+    let weeks = by_week
+        .iter()
+        .map(|(week, reports)| {
+            json!({
+                "week": week,
+                "bench_king": reports.first().map(|r| labels.label(r.roster_id, &r.owner_id)),
+                "difference": reports.first().map(|r| r.difference()).unwrap_or_default(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let leaderboard = calculate_efficiency_leaderboard(reports.to_vec());
+
+    let render = |template: &str, file_name: String, title: String, mut data: Value| {
+        data["title"] = json!(title);
+        data["league"] = json!(league_name);
+        data["season"] = json!(season);
+        data["weeks"] = json!(weeks);
+        handlebars
+            .render(template, &data)
+            .map(|html| DashboardPage { file_name, html })
+            .map_err(|e| format!("Unable to render {}: {}", template, e))
+    };
+
+    let mut pages = vec![render(
+        "index",
+        "index.html".to_string(),
+        format!("{} {} Bench King", league_name, season),
+        json!({
+            "bench_chart": cumulative_bench_chart("Points Left on the Bench", &leaderboard, labels)
+                .line_svg(),
+            "leaderboard": leaderboard
+                .iter()
+                .enumerate()
+                .map(|(idx, s)| {
+                    let mut row = report_row(&s.season_report(), labels);
+                    row["rank"] = json!(idx + 1);
+                    row["trend"] = json!(s.efficiency_trend());
+                    row
+                })
+                .collect::<Vec<_>>(),
+        }),
+    )?];
+    for (week, reports) in &by_week {
+        pages.push(render(
+            "week",
+            format!("week-{}.html", week),
+            format!("Week {} Bench King", week),
+            json!({
+                "rows": reports
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, r)| {
+                        let mut row = report_row(r, labels);
+                        row["rank"] = json!(idx + 1);
+                        row
+                    })
+                    .collect::<Vec<_>>(),
+            }),
+        )?);
+    }
+    for summary in &leaderboard {
+        pages.push(render(
+            "manager",
+            manager_page(summary.roster_id),
+            labels.label(summary.roster_id, &summary.owner_id()),
+            json!({
+                "efficiency": summary.efficiency(),
+                "mean_efficiency": summary.mean_weekly_efficiency(),
+                "median_efficiency": summary.median_weekly_efficiency(),
+                "difference": summary.difference(),
+                "efficiency_chart": efficiency_trend_chart("Lineup Efficiency", summary).line_svg(),
+                "points_chart": optimal_vs_actual_chart("Optimal vs Actual Points", &summary.weeks)
+                    .bar_svg(),
+                "rows": summary
+                    .weeks
+                    .iter()
+                    .map(|r| report_row(r, labels))
+                    .collect::<Vec<_>>(),
+            }),
+        )?);
+    }
+    Ok(pages)
+}
+
+/// Writes `pages` into `dir`, creating it if needed.
+pub fn write_dashboard(dir: impl AsRef<Path>, pages: &[DashboardPage]) -> Result<(), String> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Unable to create {}: {}", dir.display(), e))?;
+    for page in pages {
+        let path = dir.join(&page.file_name);
+        std::fs::write(&path, &page.html)
+            .map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn renders_pages() {
+        let reports = vec![
+            Report::from_points(1, 1, 100.0, 120.0),
+            Report::from_points(1, 2, 90.0, 95.0),
+            Report::from_points(2, 1, 110.0, 110.0),
+            Report::from_points(2, 2, 80.0, 100.0),
+        ];
+        let pages = render_dashboard("Dynasty", "2024", &reports, &OwnerLabels::default()).unwrap();
+        let names = pages
+            .iter()
+            .map(|p| p.file_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "index.html",
+                "week-1.html",
+                "week-2.html",
+                "manager-1.html",
+                "manager-2.html"
+            ]
+        );
+        assert!(pages[0]
+            .html
+            .contains("<a href=\"manager-1.html\">Roster 1</a>"));
+        assert!(pages[1].html.contains("20.00"));
+        assert!(pages[3].html.contains("<svg"));
+        assert!(pages[3].html.contains("<a href=\"week-2.html\">Week 2</a>"));
+    }
+}
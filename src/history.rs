@@ -0,0 +1,408 @@
+//! SQLite store of final weeks, so past seasons can be reported on without the
+//! Sleeper API. Rosters from the API only reflect the current state, so a week
+//! is saved with the rosters, matchups and users as they were once it was final,
+//! alongside the reports calculated from them.
+
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+use crate::calculation_helpers::report::Report;
+use crate::models::{
+    league::League,
+    matchup::Matchup,
+    roster::{Roster, RosterId},
+    user::LeagueUser,
+};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS leagues (
+        league_id TEXT PRIMARY KEY,
+        season TEXT NOT NULL,
+        name TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS weeks (
+        league_id TEXT NOT NULL REFERENCES leagues (league_id),
+        week INTEGER NOT NULL,
+        rosters TEXT NOT NULL,
+        matchups TEXT NOT NULL,
+        users TEXT NOT NULL,
+        saved_at TEXT NOT NULL,
+        PRIMARY KEY (league_id, week)
+    );
+    CREATE TABLE IF NOT EXISTS reports (
+        league_id TEXT NOT NULL REFERENCES leagues (league_id),
+        week INTEGER NOT NULL,
+        roster_id INTEGER NOT NULL,
+        owner_id TEXT NOT NULL,
+        optimal_points REAL NOT NULL,
+        actual_points REAL NOT NULL,
+        achievable_points REAL,
+        PRIMARY KEY (league_id, week, roster_id)
+    );
+    CREATE INDEX IF NOT EXISTS reports_owner_id ON reports (owner_id);
+";
+
+/// Everything saved for one league week.
+#[derive(Debug, Clone)]
+pub struct WeekSnapshot {
+    pub league: League,
+    pub week: i32,
+    pub rosters: Vec<Roster>,
+    pub matchups: Vec<Matchup>,
+    pub users: Vec<LeagueUser>,
+    pub reports: Vec<Report>,
+}
+
+/// An owner's reports for one league season.
+#[derive(Debug, Clone, Serialize)]
+pub struct OwnerSeason {
+    pub league_id: String,
+    pub league_name: String,
+    pub season: String,
+    pub reports: Vec<Report>,
+}
+
+pub struct HistoryStore {
+    conn: Connection,
+}
+
+fn to_json<T: Serialize>(data: &T) -> Result<String, String> {
+    serde_json::to_string(data).map_err(|e| e.to_string())
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(data: &str) -> Result<T, String> {
+    serde_json::from_str(data).map_err(|e| e.to_string())
+}
+
+fn report_from_row(row: &rusqlite::Row) -> rusqlite::Result<Report> {
+    Ok(Report {
+        week: row.get("week")?,
+        roster_id: row.get("roster_id")?,
+        owner_id: row.get("owner_id")?,
+        optimal_points: row.get("optimal_points")?,
+        actual_points: row.get("actual_points")?,
+        achievable_points: row.get("achievable_points")?,
+    })
+}
+
+impl HistoryStore {
+    /// Opens the database at `path`, creating it and its tables if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<HistoryStore, String> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Unable to create {}: {}", dir.display(), e))?;
+        }
+        let conn = Connection::open(path)
+            .map_err(|e| format!("Unable to open {}: {}", path.display(), e))?;
+        HistoryStore::with_connection(conn)
+    }
+
+    pub fn open_in_memory() -> Result<HistoryStore, String> {
+        HistoryStore::with_connection(Connection::open_in_memory().map_err(|e| e.to_string())?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<HistoryStore, String> {
+        conn.execute_batch(SCHEMA).map_err(|e| e.to_string())?;
+        Ok(HistoryStore { conn })
+    }
+
+    /// Saves `snapshot`, replacing anything already saved for the week.
+    pub fn save_week(&mut self, snapshot: &WeekSnapshot) -> Result<(), String> {
+        let league_id = &snapshot.league.league_id;
+        let tx = self.conn.transaction().map_err(|e| e.to_string())?;
+        tx.execute(
+            "INSERT OR REPLACE INTO leagues (league_id, season, name, data) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                league_id,
+                snapshot.league.season,
+                snapshot.league.name,
+                to_json(&snapshot.league)?
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        tx.execute(
+            "INSERT OR REPLACE INTO weeks (league_id, week, rosters, matchups, users, saved_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                league_id,
+                snapshot.week,
+                to_json(&snapshot.rosters)?,
+                to_json(&snapshot.matchups)?,
+                to_json(&snapshot.users)?,
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        tx.execute(
+            "DELETE FROM reports WHERE league_id = ?1 AND week = ?2",
+            params![league_id, snapshot.week],
+        )
+        .map_err(|e| e.to_string())?;
+        for report in &snapshot.reports {
+            tx.execute(
+                "INSERT INTO reports
+                 (league_id, week, roster_id, owner_id, optimal_points, actual_points, achievable_points)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    league_id,
+                    report.week,
+                    report.roster_id,
+                    report.owner_id,
+                    report.optimal_points,
+                    report.actual_points,
+                    report.achievable_points
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        tx.commit().map_err(|e| e.to_string())
+    }
+
+    pub fn load_week(&self, league_id: &str, week: i32) -> Result<Option<WeekSnapshot>, String> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT l.data, w.rosters, w.matchups, w.users
+                 FROM weeks w JOIN leagues l ON l.league_id = w.league_id
+                 WHERE w.league_id = ?1 AND w.week = ?2",
+                params![league_id, week],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        let Some((league, rosters, matchups, users)) = row else {
+            return Ok(None);
+        };
+        Ok(Some(WeekSnapshot {
+            league: from_json(&league)?,
+            week,
+            rosters: from_json(&rosters)?,
+            matchups: from_json(&matchups)?,
+            users: from_json(&users)?,
+            reports: self.query_reports(
+                "SELECT * FROM reports WHERE league_id = ?1 AND week = ?2 ORDER BY roster_id",
+                params![league_id, week],
+            )?,
+        }))
+    }
+
+    /// Rosters saved for the week, with the owners they had then.
+    pub fn rosters_for_week(
+        &self,
+        league_id: &str,
+        week: i32,
+    ) -> Result<Option<Vec<Roster>>, String> {
+        self.conn
+            .query_row(
+                "SELECT rosters FROM weeks WHERE league_id = ?1 AND week = ?2",
+                params![league_id, week],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?
+            .map(|rosters| from_json(&rosters))
+            .transpose()
+    }
+
+    /// Weeks saved for the league, in order.
+    pub fn saved_weeks(&self, league_id: &str) -> Result<Vec<i32>, String> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT week FROM weeks WHERE league_id = ?1 ORDER BY week")
+            .map_err(|e| e.to_string())?;
+        let weeks = statement
+            .query_map(params![league_id], |row| row.get(0))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<i32>, _>>()
+            .map_err(|e| e.to_string())?;
+        Ok(weeks)
+    }
+
+    /// Every saved report for a roster, in week order.
+    pub fn weeks_for_roster(
+        &self,
+        league_id: &str,
+        roster_id: RosterId,
+    ) -> Result<Vec<Report>, String> {
+        self.query_reports(
+            "SELECT * FROM reports WHERE league_id = ?1 AND roster_id = ?2 ORDER BY week",
+            params![league_id, roster_id],
+        )
+    }
+
+    /// Every league season an owner has saved reports for, oldest first.
+    pub fn seasons_for_owner(&self, owner_id: &str) -> Result<Vec<OwnerSeason>, String> {
+        let mut statement = self
+            .conn
+            .prepare(
+                "SELECT DISTINCT l.league_id, l.name, l.season
+                 FROM reports r JOIN leagues l ON l.league_id = r.league_id
+                 WHERE r.owner_id = ?1 ORDER BY l.season, l.name",
+            )
+            .map_err(|e| e.to_string())?;
+        let leagues = statement
+            .query_map(params![owner_id], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<(String, String, String)>, _>>()
+            .map_err(|e| e.to_string())?;
+        leagues
+            .into_iter()
+            .map(|(league_id, league_name, season)| {
+                let reports = self.query_reports(
+                    "SELECT * FROM reports WHERE league_id = ?1 AND owner_id = ?2 ORDER BY week",
+                    params![league_id, owner_id],
+                )?;
+                Ok(OwnerSeason {
+                    league_id,
+                    league_name,
+                    season,
+                    reports,
+                })
+            })
+            .collect()
+    }
+
+    fn query_reports(
+        &self,
+        sql: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<Report>, String> {
+        let mut statement = self.conn.prepare(sql).map_err(|e| e.to_string())?;
+        let reports = statement
+            .query_map(params, report_from_row)
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        Ok(reports)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::calculation_helpers::calculate_bench_king_for_week::calculate_bench_king_for_week;
+    use crate::calculation_helpers::calculate_efficiency::summarize_season;
+
+    fn snapshot(week: i32, matchups: &str) -> WeekSnapshot {
+        let rosters: Vec<Roster> =
+            serde_json::from_str(include_str!("../fixtures/rosters.json")).unwrap();
+        WeekSnapshot {
+            league: serde_json::from_str(include_str!("../fixtures/league.json")).unwrap(),
+            week,
+            reports: rosters
+                .iter()
+                .map(|r| Report {
+                    week,
+                    roster_id: r.roster_id,
+                    owner_id: r.owner_id.clone(),
+                    optimal_points: 100.0 + week as f32,
+                    actual_points: 90.0,
+                    achievable_points: None,
+                })
+                .collect(),
+            rosters,
+            matchups: serde_json::from_str(matchups).unwrap(),
+            users: serde_json::from_str(include_str!("../fixtures/users.json")).unwrap(),
+        }
+    }
+
+    #[test]
+    fn saves_and_queries_weeks() {
+        let mut store = HistoryStore::open_in_memory().unwrap();
+        store
+            .save_week(&snapshot(
+                1,
+                include_str!("../fixtures/matchups_week_1.json"),
+            ))
+            .unwrap();
+        store
+            .save_week(&snapshot(
+                2,
+                include_str!("../fixtures/matchups_week_2.json"),
+            ))
+            .unwrap();
+        // Saving a week again replaces it
+        store
+            .save_week(&snapshot(
+                2,
+                include_str!("../fixtures/matchups_week_2.json"),
+            ))
+            .unwrap();
+
+        let league_id = "1100000000000000000";
+        assert_eq!(store.saved_weeks(league_id).unwrap(), vec![1, 2]);
+        let week = store.load_week(league_id, 2).unwrap().unwrap();
+        assert_eq!(week.matchups.len(), 2);
+        assert_eq!(week.users[0].display_name, "alice");
+        assert_eq!(week.reports.len(), 2);
+        assert!(store.load_week(league_id, 3).unwrap().is_none());
+
+        let roster_weeks = store.weeks_for_roster(league_id, 1).unwrap();
+        assert_eq!(
+            roster_weeks.iter().map(|r| r.week).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(roster_weeks[1].optimal_points, 102.0);
+
+        let seasons = store.seasons_for_owner("200000000000000002").unwrap();
+        assert_eq!(seasons.len(), 1);
+        assert_eq!(seasons[0].season, "2024");
+        assert_eq!(seasons[0].reports.len(), 2);
+        assert!(store.seasons_for_owner("unknown").unwrap().is_empty());
+    }
+
+    #[test]
+    fn season_uses_the_owners_saved_for_each_week() {
+        let mut store = HistoryStore::open_in_memory().unwrap();
+        let week_1 = snapshot(1, include_str!("../fixtures/matchups_week_1.json"));
+        let mut week_2 = snapshot(2, include_str!("../fixtures/matchups_week_2.json"));
+        week_2.rosters[0].owner_id = "200000000000000003".to_string();
+        store.save_week(&week_1).unwrap();
+        store.save_week(&week_2).unwrap();
+
+        let league_id = "1100000000000000000";
+        assert!(store.rosters_for_week(league_id, 3).unwrap().is_none());
+        let mut reports = vec![];
+        for snapshot in [week_1, week_2] {
+            let rosters = store
+                .rosters_for_week(league_id, snapshot.week)
+                .unwrap()
+                .unwrap();
+            reports.extend(
+                calculate_bench_king_for_week(
+                    snapshot.week,
+                    snapshot.matchups,
+                    &rosters,
+                    &Default::default(),
+                    &snapshot.league,
+                    None,
+                )
+                .unwrap(),
+            );
+        }
+        let summary = summarize_season(reports)
+            .into_iter()
+            .find(|s| s.roster_id == 1)
+            .unwrap();
+        assert_eq!(
+            summary.owner_history,
+            vec![
+                (1, "200000000000000001".to_string()),
+                (2, "200000000000000003".to_string())
+            ]
+        );
+    }
+}
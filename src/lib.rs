@@ -1,3 +1,8 @@
 pub mod calculation_helpers;
+pub mod charts;
 pub mod client;
+pub mod dashboard;
+pub mod history;
 pub mod models;
+pub mod recap;
+pub mod webhook;
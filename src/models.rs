@@ -1,9 +1,14 @@
+pub mod draft;
+pub mod injury;
 pub mod league;
 pub mod matchup;
 pub mod player;
 pub mod playoff;
 pub mod positions;
 pub mod roster;
+pub mod schedule;
 pub mod settings;
 pub mod state;
+pub mod stats;
+pub mod transaction;
 pub mod user;
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::user::UserId;
+
+// {
+//     "type": "snake",
+//     "status": "complete",
+//     "start_time": 1515700800000,
+//     "sport": "nfl",
+//     "settings": { "teams": 6, "slots_wr": 2, "rounds": 15, "pick_timer": 120, ... },
+//     "season_type": "regular",
+//     "season": "2017",
+//     "metadata": { "scoring_type": "ppr", "name": "My Dynasty", "description": "" },
+//     "league_id": "257270637750382592",
+//     "last_picked": 1515700871182,
+//     "draft_order": { "12345678": 1, "23434332": 2 },
+//     "draft_id": "257270643320426496",
+//     "created": 1515700610526,
+//     "slot_to_roster_id": { "1": 10, "2": 3 }
+//   }
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Draft {
+    #[serde(rename = "type")]
+    pub draft_type: String,
+    pub status: String,
+    pub start_time: Option<u64>,
+    pub sport: String,
+    pub settings: DraftSettings,
+    pub season_type: String,
+    pub season: String,
+    pub league_id: Option<String>,
+    pub last_picked: Option<u64>,
+    /// User id to draft slot
+    pub draft_order: Option<HashMap<UserId, u16>>,
+    pub draft_id: String,
+    pub created: Option<u64>,
+    /// Draft slot to roster id
+    pub slot_to_roster_id: Option<HashMap<String, u32>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DraftSettings {
+    pub teams: u16,
+    pub rounds: u16,
+    pub pick_timer: Option<u32>,
+}
+
+// {
+//     "player_id": "2391",
+//     "picked_by": "234343434", // user id
+//     "roster_id": "1",
+//     "round": 5,
+//     "draft_slot": 5,
+//     "pick_no": 1,
+//     "metadata": { "team": "ARI", "status": "Active", "position": "RB", "last_name": "Johnson", "first_name": "David", ... },
+//     "is_keeper": null,
+//     "draft_id": "257270643320426496"
+//   }
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DraftPick {
+    pub player_id: String,
+    pub picked_by: UserId,
+    /// Sent as a string by the API
+    pub roster_id: Option<String>,
+    pub round: u16,
+    pub draft_slot: u16,
+    pub pick_no: u16,
+    pub metadata: Option<DraftPickMetadata>,
+    pub is_keeper: Option<bool>,
+    pub draft_id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DraftPickMetadata {
+    pub team: Option<String>,
+    pub status: Option<String>,
+    pub position: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+}
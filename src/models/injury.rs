@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::player::Players;
+
+// The players endpoint only returns today's injury status, so statuses are
+// snapshotted to disk each week, before lock, for later analysis:
+// {
+//     "week": 3,
+//     "season": "2024",
+//     "statuses": { "4034": "Out", "6794": "Questionable" }
+// }
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct InjurySnapshot {
+    pub week: i32,
+    pub season: String,
+    /// `PlayerDetails::injury_status` by player id, for players with a status
+    pub statuses: HashMap<String, String>,
+}
+
+impl InjurySnapshot {
+    /// Captures the current injury status of every player.
+    pub fn from_players(season: String, week: i32, players: &Players) -> InjurySnapshot {
+        let statuses = players
+            .iter()
+            .filter_map(|(player_id, details)| {
+                details
+                    .injury_status
+                    .clone()
+                    .map(|status| (player_id.clone(), status))
+            })
+            .collect();
+        InjurySnapshot {
+            week,
+            season,
+            statuses,
+        }
+    }
+
+    pub fn path(dir: &str, season: &str, week: i32) -> PathBuf {
+        Path::new(dir)
+            .join(season)
+            .join(format!("injuries_week_{}.json", week))
+    }
+
+    pub fn load(dir: &str, season: &str, week: i32) -> Result<InjurySnapshot, String> {
+        let data = std::fs::read_to_string(InjurySnapshot::path(dir, season, week))
+            .map_err(|e| e.to_string())?;
+        serde_json::from_str(&data).map_err(|e| e.to_string())
+    }
+
+    pub fn save(&self, dir: &str) -> Result<(), String> {
+        let path = InjurySnapshot::path(dir, &self.season, self.week);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let data = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, data).map_err(|e| e.to_string())
+    }
+
+    pub fn status(&self, player_id: &str) -> Option<&String> {
+        self.statuses.get(player_id)
+    }
+
+    /// Whether the player was listed as unlikely to play: Out, Doubtful, or on a
+    /// designation (IR, PUP, suspension, ...) that keeps them off the field.
+    pub fn is_unlikely_to_play(&self, player_id: &str) -> bool {
+        matches!(
+            self.status(player_id).map(|s| s.as_str()),
+            Some("Out" | "Doubtful" | "IR" | "PUP" | "Sus" | "COV" | "NA" | "DNR")
+        )
+    }
+}
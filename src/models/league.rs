@@ -28,9 +28,16 @@ pub struct League {
     pub avatar: Option<String>,
     pub company_id: Option<String>,
 }
+impl League {
+    /// Best-ball leagues auto-start the optimal lineup every week.
+    pub fn is_best_ball(&self) -> bool {
+        self.settings.best_ball.unwrap_or(0) == 1
+    }
+}
+
 impl Display for League {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "League: {}", self)
+        write!(f, "League: {}", self.name)
     }
 }
 
@@ -86,6 +93,6 @@ pub struct LeagueSettings {
 
 impl Display for LeagueSettings {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "League Settings: {}", self)
+        write!(f, "League Settings: {:?}", self)
     }
 }
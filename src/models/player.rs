@@ -58,6 +58,18 @@ pub struct PlayerDetails {
     pub years_exp: Option<u8>,
 }
 
+impl PlayerDetails {
+    /// Full name, falling back to first and last name (team defenses only have
+    /// those) and then the player id.
+    pub fn name(&self) -> String {
+        match (&self.full_name, &self.first_name, &self.last_name) {
+            (Some(full_name), _, _) => full_name.clone(),
+            (None, Some(first), Some(last)) => format!("{} {}", first, last),
+            _ => self.player_id.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PlayerMetadata {
     pub channel_id: Option<String>,
@@ -83,3 +95,57 @@ pub struct TrendingPlayer {
     pub player_id: String,
     pub count: u32,
 }
+
+/// A trending player joined with their details.
+#[derive(Debug, Serialize, Clone)]
+pub struct TrendingPlayerDetails {
+    pub player_id: String,
+    pub count: u32,
+    pub name: String,
+    pub team: Option<String>,
+    pub position: Option<RosterPosition>,
+    pub injury_status: Option<String>,
+}
+
+impl TrendingPlayer {
+    /// Players missing from `players` keep their id as their name.
+    pub fn with_details(&self, players: &Players) -> TrendingPlayerDetails {
+        let details = players.get(&self.player_id);
+        TrendingPlayerDetails {
+            player_id: self.player_id.clone(),
+            count: self.count,
+            name: details.map(|p| p.name()).unwrap_or(self.player_id.clone()),
+            team: details.and_then(|p| p.team.clone()),
+            position: details.and_then(|p| p.position),
+            injury_status: details.and_then(|p| p.injury_status.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn joins_trending_players_with_details() {
+        let mut players: Players =
+            serde_json::from_str(include_str!("../../fixtures/players.json")).unwrap();
+        players.get_mut("1466").unwrap().injury_status = Some("Questionable".to_string());
+        let trending: Vec<TrendingPlayer> = serde_json::from_str(
+            r#"[{"player_id": "1466", "count": 120}, {"player_id": "7000", "count": 3}]"#,
+        )
+        .unwrap();
+
+        let details = trending[0].with_details(&players);
+        assert_eq!(details.name, "Travis Kelce");
+        assert_eq!(details.count, 120);
+        assert_eq!(details.team.as_deref(), Some("KC"));
+        assert_eq!(details.position, Some(RosterPosition::TE));
+        assert_eq!(details.injury_status.as_deref(), Some("Questionable"));
+
+        let details = trending[1].with_details(&players);
+        assert_eq!(details.name, "7000");
+        assert_eq!(details.team, None);
+        assert_eq!(details.position, None);
+    }
+}
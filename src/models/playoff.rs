@@ -30,7 +30,7 @@ pub struct Playoff {
 
 impl Display for Playoff {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Playoff: {}", self)
+        write!(f, "Playoff: {:?}", self)
     }
 }
 
@@ -1,7 +1,9 @@
-use std::vec;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
+/// A lineup slot from `League::roster_positions`, or a player's position from
+/// `PlayerDetails::fantasy_positions`.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Hash, Copy)]
 pub enum RosterPosition {
     BN,
@@ -13,6 +15,7 @@ pub enum RosterPosition {
     DL,
     DT,
     FB,
+    #[serde(alias = "WRT")]
     FLEX,
     FS,
     G,
@@ -33,6 +36,8 @@ pub enum RosterPosition {
     P,
     QB,
     RB,
+    #[serde(alias = "REC_FLEX")]
+    RecFlex,
     S,
     SS,
     #[serde(alias = "SUPER_FLEX")]
@@ -44,48 +49,152 @@ pub enum RosterPosition {
     WrRbFlex,
 }
 
+use RosterPosition::*;
+
+const DEFENSIVE_LINE: &[RosterPosition] = &[DL, DE, DT, NT, LEO];
+const LINEBACKERS: &[RosterPosition] = &[LB, ILB, OLB, LEO];
+const DEFENSIVE_BACKS: &[RosterPosition] = &[DB, CB, S, FS, SS];
+const IDP: &[RosterPosition] = &[DL, DE, DT, NT, LEO, LB, ILB, OLB, DB, CB, S, FS, SS];
+const OFFENSIVE_LINE: &[RosterPosition] = &[OL, OT, OG, T, G, C];
+
 impl RosterPosition {
-    pub fn value(&self) -> Vec<String> {
+    /// Player positions that can start in this slot. The bench holds anyone,
+    /// see `can_hold`.
+    pub fn eligible_positions(&self) -> &'static [RosterPosition] {
         match self {
-            RosterPosition::BN => vec!["BN".to_string()],   // Bench
-            RosterPosition::C => vec!["C".to_string()],     // Center
-            RosterPosition::CB => vec!["CB".to_string()],   // Cornerback
-            RosterPosition::DB => vec!["DB".to_string()],   // Defensive Back
-            RosterPosition::DE => vec!["DE".to_string()],   // Defensive End
-            RosterPosition::DEF => vec!["DEF".to_string()], // Defense (Team)
-            RosterPosition::DL => vec!["DL".to_string()],   // Defensive Line
-            RosterPosition::DT => vec!["DT".to_string()],   // Defensive Tackle
-            RosterPosition::FB => vec!["FB".to_string()],   // Fullback
-            RosterPosition::FLEX => vec!["WR".to_string(), "RB".to_string(), "TE".to_string()], // WR/RB/TE Flex
-            RosterPosition::FS => vec!["FS".to_string()], // Free Safety
-            RosterPosition::G => vec!["G".to_string()],   // Guard
-            RosterPosition::IdpFlex => vec!["IDP".to_string()], // IDP Flex
-            RosterPosition::ILB => vec!["ILB".to_string()], // Inside Linebacker
-            RosterPosition::K => vec!["K".to_string()],   // Kicker
-            RosterPosition::KP => vec!["K/P".to_string()], // Kicker/Punter
-            RosterPosition::LB => vec!["LB".to_string()], // Linebacker
-            RosterPosition::LEO => vec!["LEO".to_string()], // TODO: Is LEO included in fanatsy matchup data? LEO ~= LB + DE
-            RosterPosition::LS => vec!["LS".to_string()],   // Long Snapper
-            RosterPosition::NT => vec!["NT".to_string()],   // Nose Tackle
-            RosterPosition::OG => vec!["OG".to_string()],   // Offensive Guard
-            RosterPosition::OL => vec!["OL".to_string()],   // Offensive Line
-            RosterPosition::OLB => vec!["OLB".to_string()], // Outside Linebacker
-            RosterPosition::OT => vec!["OT".to_string()],   // Offensive Tackle
-            RosterPosition::P => vec!["P".to_string()],     // Punter
-            RosterPosition::QB => vec!["QB".to_string()],   // Quarterback
-            RosterPosition::RB => vec!["RB".to_string()],   // Running Back
-            RosterPosition::S => vec!["S".to_string()],     // Safety
-            RosterPosition::SS => vec!["SS".to_string()],   // Strong Safety
-            RosterPosition::SuperFlex => vec![
-                "WR".to_string(),
-                "RB".to_string(),
-                "TE".to_string(),
-                "QB".to_string(),
-            ], // WR/RB/TE/QB Flex
-            RosterPosition::T => vec!["T".to_string()],     // Tackle
-            RosterPosition::TE => vec!["TE".to_string()],   // Tight End
-            RosterPosition::WR => vec!["WR".to_string()],   // Wide Receiver
-            RosterPosition::WrRbFlex => vec!["WR".to_string(), "RB".to_string()], // WR/RB Flex
+            BN => &[],
+            FLEX => &[RB, WR, TE],
+            SuperFlex => &[QB, RB, WR, TE],
+            WrRbFlex => &[WR, RB],
+            RecFlex => &[WR, TE],
+            KP => &[K, P],
+            DL => DEFENSIVE_LINE,
+            LB => LINEBACKERS,
+            DB => DEFENSIVE_BACKS,
+            S => &[S, FS, SS],
+            IdpFlex => IDP,
+            OL => OFFENSIVE_LINE,
+            C => &[C],
+            CB => &[CB],
+            DE => &[DE],
+            DEF => &[DEF],
+            DT => &[DT],
+            FB => &[FB],
+            FS => &[FS],
+            G => &[G],
+            ILB => &[ILB],
+            K => &[K],
+            LEO => &[LEO],
+            LS => &[LS],
+            NT => &[NT],
+            OG => &[OG],
+            OLB => &[OLB],
+            OT => &[OT],
+            P => &[P],
+            QB => &[QB],
+            RB => &[RB],
+            SS => &[SS],
+            T => &[T],
+            TE => &[TE],
+            WR => &[WR],
+        }
+    }
+
+    /// Whether a player at `position` can fill this slot.
+    pub fn can_hold(&self, position: &RosterPosition) -> bool {
+        *self == BN || self.eligible_positions().contains(position)
+    }
+
+    /// Slots shared between position groups, filled after the dedicated ones.
+    pub fn is_flex(&self) -> bool {
+        matches!(self, FLEX | SuperFlex | WrRbFlex | RecFlex | IdpFlex)
+    }
+}
+
+impl FromStr for RosterPosition {
+    type Err = String;
+
+    /// Parses Sleeper's names, e.g. `"QB"`, `"K/P"` or `"IDP_FLEX"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string()))
+            .map_err(|_| format!("Unknown roster position {}", s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn slot(name: &str) -> RosterPosition {
+        name.parse().unwrap()
+    }
+
+    #[test]
+    fn offensive_slots() {
+        assert_eq!(slot("QB").eligible_positions(), [QB]);
+        assert_eq!(slot("RB").eligible_positions(), [RB]);
+        assert_eq!(slot("WR").eligible_positions(), [WR]);
+        assert_eq!(slot("TE").eligible_positions(), [TE]);
+        assert_eq!(slot("K").eligible_positions(), [K]);
+        assert_eq!(slot("DEF").eligible_positions(), [DEF]);
+        assert_eq!(slot("FLEX").eligible_positions(), [RB, WR, TE]);
+        assert_eq!(slot("WRT"), FLEX);
+        assert_eq!(slot("SUPER_FLEX").eligible_positions(), [QB, RB, WR, TE]);
+        assert_eq!(slot("WRRB_FLEX").eligible_positions(), [WR, RB]);
+        assert_eq!(slot("REC_FLEX").eligible_positions(), [WR, TE]);
+        assert_eq!(slot("K/P").eligible_positions(), [K, P]);
+        assert!(!slot("REC_FLEX").can_hold(&RB));
+        assert!(!slot("FLEX").can_hold(&QB));
+        assert!(slot("BN").can_hold(&QB));
+        assert!(slot("BN").eligible_positions().is_empty());
+    }
+
+    #[test]
+    fn idp_slots() {
+        for position in [DL, DE, DT, NT] {
+            assert!(slot("DL").can_hold(&position));
+        }
+        for position in [LB, ILB, OLB] {
+            assert!(slot("LB").can_hold(&position));
+        }
+        for position in [DB, CB, S, FS, SS] {
+            assert!(slot("DB").can_hold(&position));
         }
+        assert!(!slot("DL").can_hold(&CB));
+        assert!(!slot("DB").can_hold(&LB));
+        assert!(!slot("LB").can_hold(&DT));
+        assert!(slot("S").can_hold(&SS));
+        assert!(!slot("CB").can_hold(&S));
+        for position in DEFENSIVE_LINE
+            .iter()
+            .chain(LINEBACKERS)
+            .chain(DEFENSIVE_BACKS)
+        {
+            assert!(slot("IDP_FLEX").can_hold(position));
+        }
+        assert!(!slot("IDP_FLEX").can_hold(&DEF));
+        assert!(!slot("IDP_FLEX").can_hold(&WR));
+        assert!(slot("IDP_FLEX").is_flex());
+        assert!(!slot("DL").is_flex());
+    }
+
+    #[test]
+    fn dedicated_slots() {
+        for position in [
+            C, CB, DE, DT, FB, FS, G, ILB, LEO, LS, NT, OG, OLB, OT, P, SS, T,
+        ] {
+            assert_eq!(position.eligible_positions(), [position]);
+            assert!(!position.is_flex());
+        }
+        for position in [OT, OG, T, G, C] {
+            assert!(slot("OL").can_hold(&position));
+        }
+        assert!(slot("LEO").can_hold(&LEO));
+        assert!(slot("DL").can_hold(&LEO) && slot("LB").can_hold(&LEO));
+    }
+
+    #[test]
+    fn parses_unknown_slots_as_errors() {
+        assert!("XFLEX".parse::<RosterPosition>().is_err());
     }
 }
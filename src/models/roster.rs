@@ -30,6 +30,7 @@ pub struct Roster {
     pub settings: RosterSettings,
     pub roster_id: RosterId,
     pub reserve: Option<Vec<String>>,
+    pub taxi: Option<Vec<String>>,
     pub players: Option<Vec<String>>,
     pub player_map: Option<HashMap<String, String>>,
     pub owner_id: String,
@@ -54,6 +55,29 @@ pub struct RosterSettings {
     pub division: Option<u8>,
 }
 
+impl Roster {
+    /// Whether the player is on the roster, including its reserve and taxi squad.
+    pub fn has_player(&self, player_id: &str) -> bool {
+        self.players
+            .iter()
+            .chain(&self.reserve)
+            .chain(&self.taxi)
+            .flatten()
+            .any(|p| p == player_id)
+    }
+}
+
+impl RosterSettings {
+    /// Points for, combining the whole and decimal parts the API sends separately.
+    pub fn points_for(&self) -> f32 {
+        self.fpts as f32 + self.fpts_decimal.unwrap_or(0.0) / 100.0
+    }
+
+    pub fn points_against(&self) -> f32 {
+        self.fpts_against.unwrap_or(0) as f32 + self.fpts_against_decimal.unwrap_or(0.0) / 100.0
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct RosterMetadata {
     pub allow_pn_inactive_starters: Option<String>,
@@ -64,6 +88,26 @@ pub struct RosterMetadata {
 
 impl std::fmt::Display for RosterMetadata {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Roster Metadata: {}", self)
+        write!(f, "Roster Metadata: {:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn finds_players_on_reserve_and_taxi() {
+        let mut rosters: Vec<Roster> =
+            serde_json::from_str(include_str!("../../fixtures/rosters.json")).unwrap();
+        let roster = &mut rosters[0];
+        roster.reserve = Some(vec!["7000".to_string()]);
+        roster.taxi = Some(vec!["7001".to_string()]);
+        assert!(roster.has_player("4046"));
+        assert!(roster.has_player("7000"));
+        assert!(roster.has_player("7001"));
+        assert!(!roster.has_player("4984"));
+        roster.taxi = None;
+        assert!(!roster.has_player("7001"));
     }
 }
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Sleeper has no schedule endpoint, so the schedule is read from a local JSON file:
+// [
+//     { "week": 1, "home": "KC", "away": "BAL", "kickoff": "2024-09-06T00:20:00Z" },
+//     { "week": 1, "home": "PHI", "away": "GB", "kickoff": "2024-09-07T00:15:00Z" }
+// ]
+// Team abbreviations match `PlayerDetails::team`.
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Game {
+    pub week: i32,
+    pub home: String,
+    pub away: String,
+    pub kickoff: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct Schedule {
+    pub games: Vec<Game>,
+}
+
+impl Schedule {
+    pub fn from_file(path: &str) -> Result<Schedule, String> {
+        let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&data).map_err(|e| e.to_string())
+    }
+
+    pub fn game(&self, week: i32, team: &str) -> Option<&Game> {
+        self.games
+            .iter()
+            .find(|g| g.week == week && (g.home == team || g.away == team))
+    }
+
+    pub fn kickoff(&self, week: i32, team: &str) -> Option<DateTime<Utc>> {
+        self.game(week, team).map(|g| g.kickoff)
+    }
+
+    /// A team is on bye when the schedule covers the week but has no game for it.
+    pub fn is_bye(&self, week: i32, team: &str) -> bool {
+        self.games.iter().any(|g| g.week == week) && self.game(week, team).is_none()
+    }
+
+    /// The kickoff time shared by the most games in a week, i.e. the Sunday
+    /// early slate. Earlier games (Thursday night, London) kick off before it.
+    pub fn main_slate_kickoff(&self, week: i32) -> Option<DateTime<Utc>> {
+        let mut counts: HashMap<DateTime<Utc>, usize> = HashMap::new();
+        for game in self.games.iter().filter(|g| g.week == week) {
+            *counts.entry(game.kickoff).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .max_by_key(|(kickoff, count)| (*count, std::cmp::Reverse(*kickoff)))
+            .map(|(kickoff, _)| kickoff)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn main_slate_and_byes() {
+        let schedule: Schedule = serde_json::from_str(
+            r#"[
+                { "week": 1, "home": "KC", "away": "BAL", "kickoff": "2024-09-06T00:20:00Z" },
+                { "week": 1, "home": "ATL", "away": "PIT", "kickoff": "2024-09-08T17:00:00Z" },
+                { "week": 1, "home": "BUF", "away": "ARI", "kickoff": "2024-09-08T17:00:00Z" },
+                { "week": 1, "home": "SEA", "away": "DEN", "kickoff": "2024-09-08T20:05:00Z" }
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            schedule.main_slate_kickoff(1).unwrap().to_rfc3339(),
+            "2024-09-08T17:00:00+00:00"
+        );
+        assert!(schedule.kickoff(1, "BAL").is_some());
+        assert!(schedule.is_bye(1, "DET"));
+        assert!(!schedule.is_bye(2, "DET"));
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::stats::StatLine;
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ScoringSettings {
     pub blk_kick: f32,
@@ -57,3 +59,68 @@ pub struct ScoringSettings {
     pub xpm: f32,
     pub xpmiss: f32,
 }
+
+impl ScoringSettings {
+    /// Points per unit of each stat the league scores, `None` for the stats
+    /// it doesn't.
+    pub fn weights(&self) -> StatLine {
+        let scored = |weight: f32| (weight != 0.0).then_some(weight);
+        StatLine {
+            blk_kick: scored(self.blk_kick),
+            def_st_ff: scored(self.def_st_ff),
+            def_st_fum_rec: scored(self.def_st_fum_rec),
+            def_st_td: scored(self.def_st_td),
+            def_td: scored(self.def_td),
+            ff: scored(self.ff),
+            fgm_0_19: scored(self.fgm_0_19),
+            fgm_20_29: scored(self.fgm_20_29),
+            fgm_30_39: scored(self.fgm_30_39),
+            fgm_40_49: scored(self.fgm_40_49),
+            fgm_50p: scored(self.fgm_50p),
+            fgmiss: scored(self.fgmiss),
+            fum: scored(self.fum),
+            fum_lost: scored(self.fum_lost),
+            fum_rec: scored(self.fum_rec),
+            fum_rec_td: scored(self.fum_rec_td),
+            int: scored(self.int),
+            pass_2pt: scored(self.pass_2pt),
+            pass_int: scored(self.pass_int),
+            pass_td: scored(self.pass_td),
+            pass_yd: scored(self.pass_yd),
+            pts_allow_0: scored(self.pts_allow_0),
+            pts_allow_14_20: scored(self.pts_allow_14_20),
+            pts_allow_1_6: scored(self.pts_allow_1_6),
+            pts_allow_21_27: scored(self.pts_allow_21_27),
+            pts_allow_28_34: scored(self.pts_allow_28_34),
+            pts_allow_35p: scored(self.pts_allow_35p),
+            pts_allow_7_13: scored(self.pts_allow_7_13),
+            rec: scored(self.rec),
+            rec_2pt: scored(self.rec_2pt),
+            rec_td: scored(self.rec_td),
+            rec_yd: scored(self.rec_yd),
+            rush_2pt: scored(self.rush_2pt),
+            rush_td: scored(self.rush_td),
+            rush_yd: scored(self.rush_yd),
+            sack: scored(self.sack),
+            safe: scored(self.safe),
+            st_ff: scored(self.st_ff),
+            st_fum_rec: scored(self.st_fum_rec),
+            st_td: scored(self.st_td),
+            xpm: scored(self.xpm),
+            xpmiss: scored(self.xpmiss),
+            idp_blk_kick: self.idp_blk_kick.and_then(scored),
+            idp_def_td: self.idp_def_td.and_then(scored),
+            idp_ff: self.idp_ff.and_then(scored),
+            idp_fum_rec: self.idp_fum_rec.and_then(scored),
+            idp_int: self.idp_int.and_then(scored),
+            idp_pass_def: self.idp_pass_def.and_then(scored),
+            idp_qb_hit: self.idp_qb_hit.and_then(scored),
+            idp_sack: self.idp_sack.and_then(scored),
+            idp_safe: self.idp_safe.and_then(scored),
+            idp_tkl_ast: self.idp_tkl_ast.and_then(scored),
+            idp_tkl_loss: self.idp_tkl_loss.and_then(scored),
+            idp_tkl_solo: self.idp_tkl_solo.and_then(scored),
+            other: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+// Sleeper's unofficial stats and projections endpoints return one stat line
+// per player id, and no league scoring applied:
+// {
+//     "4046": {
+//         "pass_yd": 310.0,
+//         "pass_td": 2.0,
+//         "pts_ppr": 20.6,
+//         ...
+//     }
+// }
+
+/// A player's stats, or a league's points per unit of each stat. Every stat
+/// [`ScoringSettings`](super::settings::ScoringSettings) can score has a field,
+/// the rest, like `pts_ppr`, are kept in `other`.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct StatLine {
+    pub blk_kick: Option<f32>,
+    pub def_st_ff: Option<f32>,
+    pub def_st_fum_rec: Option<f32>,
+    pub def_st_td: Option<f32>,
+    pub def_td: Option<f32>,
+    pub ff: Option<f32>,
+    pub fgm_0_19: Option<f32>,
+    pub fgm_20_29: Option<f32>,
+    pub fgm_30_39: Option<f32>,
+    pub fgm_40_49: Option<f32>,
+    pub fgm_50p: Option<f32>,
+    pub fgmiss: Option<f32>,
+    pub fum: Option<f32>,
+    pub fum_lost: Option<f32>,
+    pub fum_rec: Option<f32>,
+    pub fum_rec_td: Option<f32>,
+    pub int: Option<f32>,
+    pub pass_2pt: Option<f32>,
+    pub pass_int: Option<f32>,
+    pub pass_td: Option<f32>,
+    pub pass_yd: Option<f32>,
+    pub pts_allow_0: Option<f32>,
+    pub pts_allow_14_20: Option<f32>,
+    pub pts_allow_1_6: Option<f32>,
+    pub pts_allow_21_27: Option<f32>,
+    pub pts_allow_28_34: Option<f32>,
+    pub pts_allow_35p: Option<f32>,
+    pub pts_allow_7_13: Option<f32>,
+    pub rec: Option<f32>,
+    pub rec_2pt: Option<f32>,
+    pub rec_td: Option<f32>,
+    pub rec_yd: Option<f32>,
+    pub rush_2pt: Option<f32>,
+    pub rush_td: Option<f32>,
+    pub rush_yd: Option<f32>,
+    pub sack: Option<f32>,
+    pub safe: Option<f32>,
+    pub st_ff: Option<f32>,
+    pub st_fum_rec: Option<f32>,
+    pub st_td: Option<f32>,
+    pub xpm: Option<f32>,
+    pub xpmiss: Option<f32>,
+    pub idp_blk_kick: Option<f32>,
+    pub idp_def_td: Option<f32>,
+    pub idp_ff: Option<f32>,
+    pub idp_fum_rec: Option<f32>,
+    pub idp_int: Option<f32>,
+    pub idp_pass_def: Option<f32>,
+    pub idp_qb_hit: Option<f32>,
+    pub idp_sack: Option<f32>,
+    pub idp_safe: Option<f32>,
+    pub idp_tkl_ast: Option<f32>,
+    pub idp_tkl_loss: Option<f32>,
+    pub idp_tkl_solo: Option<f32>,
+    #[serde(flatten)]
+    pub other: HashMap<String, f32>,
+}
+
+impl StatLine {
+    /// Every stat leagues can score, by stat name.
+    pub fn scored_stats(&self) -> [(&'static str, Option<f32>); 54] {
+        [
+            ("blk_kick", self.blk_kick),
+            ("def_st_ff", self.def_st_ff),
+            ("def_st_fum_rec", self.def_st_fum_rec),
+            ("def_st_td", self.def_st_td),
+            ("def_td", self.def_td),
+            ("ff", self.ff),
+            ("fgm_0_19", self.fgm_0_19),
+            ("fgm_20_29", self.fgm_20_29),
+            ("fgm_30_39", self.fgm_30_39),
+            ("fgm_40_49", self.fgm_40_49),
+            ("fgm_50p", self.fgm_50p),
+            ("fgmiss", self.fgmiss),
+            ("fum", self.fum),
+            ("fum_lost", self.fum_lost),
+            ("fum_rec", self.fum_rec),
+            ("fum_rec_td", self.fum_rec_td),
+            ("int", self.int),
+            ("pass_2pt", self.pass_2pt),
+            ("pass_int", self.pass_int),
+            ("pass_td", self.pass_td),
+            ("pass_yd", self.pass_yd),
+            ("pts_allow_0", self.pts_allow_0),
+            ("pts_allow_14_20", self.pts_allow_14_20),
+            ("pts_allow_1_6", self.pts_allow_1_6),
+            ("pts_allow_21_27", self.pts_allow_21_27),
+            ("pts_allow_28_34", self.pts_allow_28_34),
+            ("pts_allow_35p", self.pts_allow_35p),
+            ("pts_allow_7_13", self.pts_allow_7_13),
+            ("rec", self.rec),
+            ("rec_2pt", self.rec_2pt),
+            ("rec_td", self.rec_td),
+            ("rec_yd", self.rec_yd),
+            ("rush_2pt", self.rush_2pt),
+            ("rush_td", self.rush_td),
+            ("rush_yd", self.rush_yd),
+            ("sack", self.sack),
+            ("safe", self.safe),
+            ("st_ff", self.st_ff),
+            ("st_fum_rec", self.st_fum_rec),
+            ("st_td", self.st_td),
+            ("xpm", self.xpm),
+            ("xpmiss", self.xpmiss),
+            ("idp_blk_kick", self.idp_blk_kick),
+            ("idp_def_td", self.idp_def_td),
+            ("idp_ff", self.idp_ff),
+            ("idp_fum_rec", self.idp_fum_rec),
+            ("idp_int", self.idp_int),
+            ("idp_pass_def", self.idp_pass_def),
+            ("idp_qb_hit", self.idp_qb_hit),
+            ("idp_sack", self.idp_sack),
+            ("idp_safe", self.idp_safe),
+            ("idp_tkl_ast", self.idp_tkl_ast),
+            ("idp_tkl_loss", self.idp_tkl_loss),
+            ("idp_tkl_solo", self.idp_tkl_solo),
+        ]
+    }
+}
+
+/// Response from the stats and projections endpoints, by player id.
+pub type StatLines = HashMap<String, StatLine>;
+
+/// A player's stat line for one week, recorded or projected depending on the
+/// endpoint it came from.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlayerStats {
+    pub player_id: String,
+    pub season: String,
+    pub week: i32,
+    pub stats: StatLine,
+}
+
+/// What a player recorded in one week.
+pub type PlayerWeekStats = PlayerStats;
+
+/// What a player was projected to record in one week.
+pub type PlayerProjection = PlayerStats;
+
+impl PlayerStats {
+    pub fn from_stat_lines(season: &str, week: i32, lines: StatLines) -> Vec<PlayerStats> {
+        let mut stats = lines
+            .into_iter()
+            .map(|(player_id, stats)| PlayerStats {
+                player_id,
+                season: season.to_string(),
+                week,
+                stats,
+            })
+            .collect::<Vec<_>>();
+        stats.sort_by(|a, b| a.player_id.cmp(&b.player_id));
+        stats
+    }
+
+    /// Points under the league's scoring, with weights from `ScoringSettings::weights`.
+    /// Stats the league settings don't model, like bonuses, aren't scored.
+    pub fn points(&self, weights: &StatLine) -> f32 {
+        self.stats
+            .scored_stats()
+            .into_iter()
+            .zip(weights.scored_stats())
+            .filter_map(|((_, value), (_, weight))| Some(value? * weight?))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::league::League;
+
+    #[test]
+    fn scores_recorded_stats_and_projections() {
+        let league: League =
+            serde_json::from_str(include_str!("../../fixtures/league.json")).unwrap();
+        let weights = league.scoring_settings.weights();
+        // Every stat the fixture league scores, and none it doesn't
+        let scored = weights
+            .scored_stats()
+            .into_iter()
+            .filter(|(_, weight)| weight.is_some())
+            .count();
+        assert_eq!(scored, 19);
+        assert_eq!(weights.rec, Some(1.0));
+        assert_eq!(weights.pass_yd, Some(0.04));
+        assert_eq!(weights.blk_kick, None);
+        assert_eq!(weights.idp_sack, None);
+
+        let lines: StatLines =
+            serde_json::from_str(include_str!("../../fixtures/stats_week_1.json")).unwrap();
+        let stats = PlayerWeekStats::from_stat_lines("2024", 1, lines);
+        assert_eq!(stats.len(), 4);
+        assert_eq!(stats[0].player_id, "4046");
+        assert_eq!(stats[0].stats.pass_yd, Some(310.0));
+        assert_eq!(stats[0].stats.other["pass_att"], 34.0);
+        // Full PPR, so league scoring matches Sleeper's own PPR points
+        for line in &stats[..3] {
+            let expected = line.stats.other["pts_ppr"];
+            assert!((line.points(&weights) - expected).abs() < 0.01);
+        }
+        assert_eq!(stats[3].points(&weights), 0.0);
+
+        let lines: StatLines =
+            serde_json::from_str(include_str!("../../fixtures/projections_week_1.json")).unwrap();
+        let projections = PlayerProjection::from_stat_lines("2024", 1, lines);
+        assert_eq!(projections.len(), 2);
+        assert!((projections[1].points(&weights) - 11.13).abs() < 0.01);
+    }
+}
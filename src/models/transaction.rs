@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::roster::RosterId;
+
+// {
+//     "type": "trade",
+//     "transaction_id": "434852362033561600",
+//     "status_updated": 1558039402803,
+//     "status": "complete",
+//     "settings": null, // trades do not use this field
+//     "roster_ids": [2, 1], // roster_ids involved in this transaction
+//     "metadata": null,
+//     "leg": 1, // in football, this is the week
+//     "drops": null,
+//     "draft_picks": [{ "season": "2019", "round": 5, "roster_id": 1, "previous_owner_id": 1, "owner_id": 2 }],
+//     "creator": "160000000000000000", // user id who initiated the transaction
+//     "created": 1558039391576,
+//     "consenter_ids": [2, 1], // roster_ids of the people who agreed to this transaction
+//     "adds": null,
+//     "waiver_budget": [{ "sender": 2, "receiver": 3, "amount": 55 }]
+//   }
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionType {
+    Trade,
+    FreeAgent,
+    Waiver,
+    Commissioner,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Transaction {
+    #[serde(rename = "type")]
+    pub transaction_type: TransactionType,
+    pub transaction_id: String,
+    pub status_updated: Option<u64>,
+    pub status: String,
+    pub settings: Option<TransactionSettings>,
+    pub roster_ids: Vec<RosterId>,
+    /// In football, the week the transaction was made in
+    pub leg: i32,
+    /// Player id to the roster id that dropped them
+    pub drops: Option<HashMap<String, RosterId>>,
+    /// Player id to the roster id that added them
+    pub adds: Option<HashMap<String, RosterId>>,
+    pub draft_picks: Vec<TradedPick>,
+    pub creator: String,
+    pub created: u64,
+    pub consenter_ids: Option<Vec<RosterId>>,
+    pub waiver_budget: Vec<WaiverBudgetTransfer>,
+}
+
+impl Transaction {
+    pub fn is_complete(&self) -> bool {
+        self.status == "complete"
+    }
+
+    /// Player ids added by `roster_id` in this transaction.
+    pub fn added_by(&self, roster_id: RosterId) -> Vec<String> {
+        players_for_roster(&self.adds, roster_id)
+    }
+
+    /// Player ids dropped by `roster_id` in this transaction.
+    pub fn dropped_by(&self, roster_id: RosterId) -> Vec<String> {
+        players_for_roster(&self.drops, roster_id)
+    }
+}
+
+fn players_for_roster(
+    moves: &Option<HashMap<String, RosterId>>,
+    roster_id: RosterId,
+) -> Vec<String> {
+    let mut players = moves
+        .iter()
+        .flatten()
+        .filter(|(_, r)| **r == roster_id)
+        .map(|(player_id, _)| player_id.clone())
+        .collect::<Vec<_>>();
+    players.sort();
+    players
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TransactionSettings {
+    pub waiver_bid: Option<u32>,
+    pub seq: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TradedPick {
+    pub season: String,
+    pub round: u8,
+    /// The roster the pick originally belonged to
+    pub roster_id: RosterId,
+    pub previous_owner_id: RosterId,
+    pub owner_id: RosterId,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct WaiverBudgetTransfer {
+    pub sender: RosterId,
+    pub receiver: RosterId,
+    pub amount: u32,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn deserialize_trade() {
+        let transaction: Transaction = serde_json::from_str(
+            r#"{
+                "type": "trade",
+                "transaction_id": "434852362033561600",
+                "status_updated": 1558039402803,
+                "status": "complete",
+                "settings": null,
+                "roster_ids": [2, 1],
+                "metadata": null,
+                "leg": 1,
+                "drops": {"1234": 1, "4567": 2},
+                "draft_picks": [{ "season": "2019", "round": 5, "roster_id": 1, "previous_owner_id": 1, "owner_id": 2 }],
+                "creator": "160000000000000000",
+                "created": 1558039391576,
+                "consenter_ids": [2, 1],
+                "adds": {"1234": 2, "4567": 1},
+                "waiver_budget": [{ "sender": 2, "receiver": 3, "amount": 55 }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(transaction.transaction_type, TransactionType::Trade);
+        assert_eq!(transaction.added_by(2), vec!["1234"]);
+        assert_eq!(transaction.dropped_by(2), vec!["4567"]);
+    }
+}
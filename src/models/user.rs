@@ -62,17 +62,17 @@ pub struct LeagueUserMetadata {
 
 impl Display for LeagueUser {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "League User: {}", self)
+        write!(f, "League User: {}", self.display_name)
     }
 }
 impl Display for LeagueUserMetadata {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "League User Metadata: {}", self)
+        write!(f, "League User Metadata: {:?}", self)
     }
 }
 
 impl Display for User {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "User: {}", self)
+        write!(f, "User: {}", self.display_name)
     }
 }
@@ -0,0 +1,117 @@
+//! Renders a [WeeklyRecap] through a Handlebars template.
+//!
+//! Templates see the recap's fields as serialized, e.g. `{{bench_king.manager}}`,
+//! and can format points to two decimals with `{{points bench_king.optimal_points}}`.
+//! Sections that may be missing for a week (no games, no bench blunders) should
+//! be wrapped in `{{#if ...}}`. `{{{bench_chart}}}` is an inline SVG bar chart
+//! of every manager's points left on the bench.
+
+use handlebars::{handlebars_helper, no_escape, Handlebars};
+
+use crate::calculation_helpers::calculate_weekly_recap::WeeklyRecap;
+use crate::charts::{Chart, Series};
+
+const MARKDOWN_TEMPLATE: &str = include_str!("../templates/recap.md.hbs");
+const HTML_TEMPLATE: &str = include_str!("../templates/recap.html.hbs");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecapFormat {
+    Markdown,
+    Html,
+}
+
+impl RecapFormat {
+    pub fn default_template(&self) -> &'static str {
+        match self {
+            RecapFormat::Markdown => MARKDOWN_TEMPLATE,
+            RecapFormat::Html => HTML_TEMPLATE,
+        }
+    }
+}
+
+handlebars_helper!(points: |value: f64| format!("{:.2}", value));
+
+fn bench_chart(recap: &WeeklyRecap) -> Chart {
+    Chart {
+        title: format!("Week {} Points Left on the Bench", recap.week),
+        categories: recap.benches.iter().map(|b| b.manager.clone()).collect(),
+        series: vec![Series {
+            name: "Left on bench".to_string(),
+            values: recap
+                .benches
+                .iter()
+                .map(|b| b.points_left_on_bench)
+                .collect(),
+        }],
+        percent: false,
+    }
+}
+
+/// Renders `recap` with `template`, or the built-in template for `format` when
+/// none is given. Values are HTML escaped only for [RecapFormat::Html].
+pub fn render_recap(
+    recap: &WeeklyRecap,
+    template: Option<&str>,
+    format: RecapFormat,
+) -> Result<String, String> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(false);
+    if format == RecapFormat::Markdown {
+        handlebars.register_escape_fn(no_escape);
+    }
+    handlebars.register_helper("points", Box::new(points));
+    let mut data = serde_json::to_value(recap).map_err(|e| e.to_string())?;
+    data["bench_chart"] = serde_json::Value::String(bench_chart(recap).bar_svg());
+    handlebars
+        .render_template(template.unwrap_or(format.default_template()), &data)
+        .map_err(|e| format!("Unable to render recap: {}", e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::calculation_helpers::calculate_weekly_recap::{RecapBenchKing, RecapTeam};
+
+    #[test]
+    fn renders_templates() {
+        let bench_king = RecapBenchKing {
+            roster_id: 2,
+            manager: "Bench Warmers".to_string(),
+            actual_points: 90.0,
+            optimal_points: 112.5,
+            points_left_on_bench: 22.5,
+        };
+        let recap = WeeklyRecap {
+            week: 4,
+            bench_king: Some(bench_king.clone()),
+            benches: vec![bench_king],
+            highest_scorer: Some(RecapTeam {
+                roster_id: 1,
+                manager: "Tom & Jerry".to_string(),
+                points: 131.456,
+            }),
+            lowest_scorer: None,
+            closest_game: None,
+            biggest_blowout: None,
+            best_performance: None,
+            biggest_blunder: None,
+        };
+        let markdown = render_recap(&recap, None, RecapFormat::Markdown).unwrap();
+        assert!(markdown.starts_with("# Week 4 Recap"));
+        assert!(markdown.contains("Highest scorer: Tom & Jerry with 131.46"));
+        assert!(markdown.contains("leaving 22.50 points on the bench"));
+        assert!(!markdown.contains("Biggest Bench Blunder"));
+
+        let html = render_recap(&recap, None, RecapFormat::Html).unwrap();
+        assert!(html.contains("Tom &amp; Jerry"));
+        assert!(html.contains("<svg"));
+
+        let custom = render_recap(
+            &recap,
+            Some("Week {{week}}: {{highest_scorer.manager}}"),
+            RecapFormat::Markdown,
+        )
+        .unwrap();
+        assert_eq!(custom, "Week 4: Tom & Jerry");
+    }
+}
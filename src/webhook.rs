@@ -0,0 +1,210 @@
+//! Posts bench king reports to Discord or Slack compatible incoming webhooks.
+
+use serde_json::{json, Value};
+
+use crate::calculation_helpers::report::{round_to_two_decimals, OwnerLabels, Report};
+
+/// Discord allows 25 fields per embed and Slack 50 blocks per message.
+const DISCORD_MAX_FIELDS: usize = 25;
+const SLACK_MAX_SECTIONS: usize = 49;
+const DISCORD_EMBED_COLOR: u32 = 0xF1C40F;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookFormat {
+    Discord,
+    Slack,
+}
+
+impl std::str::FromStr for WebhookFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "discord" => Ok(WebhookFormat::Discord),
+            "slack" => Ok(WebhookFormat::Slack),
+            _ => Err(format!(
+                "Unknown webhook format {}, expected discord or slack",
+                s
+            )),
+        }
+    }
+}
+
+/// Builds the message for `reports`, which should already be ranked.
+pub fn webhook_payload(
+    format: WebhookFormat,
+    title: &str,
+    reports: &[Report],
+    labels: &OwnerLabels,
+) -> Value {
+    match format {
+        WebhookFormat::Discord => discord_payload(title, reports, labels),
+        WebhookFormat::Slack => slack_payload(title, reports, labels),
+    }
+}
+
+fn summary(report: &Report) -> String {
+    format!(
+        "Scored {}, optimal {}, left {} on the bench ({}% efficient)",
+        round_to_two_decimals(report.actual_points),
+        round_to_two_decimals(report.optimal_points),
+        round_to_two_decimals(report.difference()),
+        round_to_two_decimals(report.efficiency() * 100.0)
+    )
+}
+
+fn discord_payload(title: &str, reports: &[Report], labels: &OwnerLabels) -> Value {
+    let fields = reports
+        .iter()
+        .take(DISCORD_MAX_FIELDS)
+        .enumerate()
+        .map(|(idx, r)| {
+            json!({
+                "name": format!("{}. {}", idx + 1, labels.label(r.roster_id, &r.owner_id)),
+                "value": summary(r),
+                "inline": false,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "embeds": [{
+            "title": title,
+            "color": DISCORD_EMBED_COLOR,
+            "fields": fields,
+        }]
+    })
+}
+
+fn slack_payload(title: &str, reports: &[Report], labels: &OwnerLabels) -> Value {
+    let mut blocks = vec![json!({
+        "type": "header",
+        "text": { "type": "plain_text", "text": title },
+    })];
+    blocks.extend(
+        reports
+            .iter()
+            .take(SLACK_MAX_SECTIONS)
+            .enumerate()
+            .map(|(idx, r)| {
+                json!({
+                    "type": "section",
+                    "text": {
+                        "type": "mrkdwn",
+                        "text": format!(
+                            "*{}. {}*\n{}",
+                            idx + 1,
+                            labels.label(r.roster_id, &r.owner_id),
+                            summary(r)
+                        ),
+                    },
+                })
+            }),
+    );
+    // `text` is the fallback shown in notifications
+    json!({ "text": title, "blocks": blocks })
+}
+
+pub async fn post_webhook(url: &str, payload: &Value) -> Result<(), String> {
+    let response = reqwest::Client::new()
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(payload.to_string())
+        .send()
+        .await
+        .map_err(|e| format!("Unable to post to webhook: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Webhook responded with {}: {}",
+            response.status(),
+            response.text().await.unwrap_or_default()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    use super::*;
+
+    /// Accepts a single request and returns its body, standing in for the chat service.
+    fn webhook_stand_in(status_line: &'static str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/webhook", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            write!(
+                stream,
+                "{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status_line
+            )
+            .unwrap();
+            String::from_utf8(body).unwrap()
+        });
+        (url, handle)
+    }
+
+    fn reports() -> Vec<Report> {
+        vec![Report {
+            week: 2,
+            roster_id: 3,
+            owner_id: "123".to_string(),
+            optimal_points: 120.0,
+            actual_points: 90.0,
+            achievable_points: None,
+        }]
+    }
+
+    #[tokio::test]
+    async fn posts_discord_embed() {
+        let (url, handle) = webhook_stand_in("HTTP/1.1 204 No Content");
+        let payload = webhook_payload(
+            WebhookFormat::Discord,
+            "Week 2 Bench King",
+            &reports(),
+            &OwnerLabels::default(),
+        );
+        post_webhook(&url, &payload).await.unwrap();
+        let body: Value = serde_json::from_str(&handle.join().unwrap()).unwrap();
+        assert_eq!(body["embeds"][0]["title"], "Week 2 Bench King");
+        assert_eq!(body["embeds"][0]["fields"][0]["name"], "1. Roster 3");
+        assert_eq!(
+            body["embeds"][0]["fields"][0]["value"],
+            "Scored 90, optimal 120, left 30 on the bench (75% efficient)"
+        );
+    }
+
+    #[tokio::test]
+    async fn slack_blocks_and_errors() {
+        let (url, handle) = webhook_stand_in("HTTP/1.1 400 Bad Request");
+        let payload = webhook_payload(
+            WebhookFormat::Slack,
+            "Week 2 Bench King",
+            &reports(),
+            &OwnerLabels::default(),
+        );
+        assert!(post_webhook(&url, &payload).await.is_err());
+        let body: Value = serde_json::from_str(&handle.join().unwrap()).unwrap();
+        assert_eq!(body["blocks"][0]["type"], "header");
+        assert_eq!(body["blocks"][1]["text"]["type"], "mrkdwn");
+        assert_eq!(body["text"], "Week 2 Bench King");
+    }
+}